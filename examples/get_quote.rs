@@ -6,7 +6,8 @@ use yahoo_finance_api as yahoo;
 fn get_quote() -> Result<f64, yahoo::YahooError> {
     let provider = yahoo::YahooConnector::new();
     // get the latest quotes in 1 minute intervals
-    let response = tokio_test::block_on(provider.get_latest_quotes("AAPL", "1d")).unwrap();
+    let response =
+        tokio_test::block_on(provider.get_latest_quotes("AAPL", yahoo::Interval::OneDay)).unwrap();
     // extract just the latest valid quote summery
     let quote = response.last_quote()?;
     Ok(quote.close)
@@ -16,7 +17,9 @@ fn get_quote() -> Result<f64, yahoo::YahooError> {
 fn get_quote() -> Result<f64, yahoo::YahooError> {
     let provider = yahoo::YahooConnector::new();
     // get the latest quotes in 1 minute intervals
-    let response = provider.get_latest_quotes("AAPL", "1d").unwrap();
+    let response = provider
+        .get_latest_quotes("AAPL", yahoo::Interval::OneDay)
+        .unwrap();
     // extract just the latest valid quote summery
     let quote = response.last_quote()?;
     Ok(quote.close)
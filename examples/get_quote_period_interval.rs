@@ -5,13 +5,23 @@ use yahoo_finance_api as yahoo;
 #[cfg(not(feature = "blocking"))]
 fn get_history() -> Result<yahoo::YResponse, yahoo::YahooError> {
     let provider = yahoo::YahooConnector::new();
-    tokio_test::block_on(provider.get_quote_period_interval("AAPL", "1d", "1m", true))
+    tokio_test::block_on(provider.get_quote_period_interval(
+        "AAPL",
+        yahoo::Range::OneDay,
+        yahoo::Interval::OneMinute,
+        true,
+    ))
 }
 
 #[cfg(feature = "blocking")]
 fn get_history() -> Result<yahoo::YResponse, yahoo::YahooError> {
     let provider = yahoo::YahooConnector::new();
-    provider.get_quote_period_interval("AAPL", "1d", "1m", true)
+    provider.get_quote_period_interval(
+        "AAPL",
+        yahoo::Range::OneDay,
+        yahoo::Interval::OneMinute,
+        true,
+    )
 }
 
 fn main() {
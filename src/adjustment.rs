@@ -0,0 +1,208 @@
+use crate::{Quote, YResponse, YahooError};
+
+/// The events the quotes are adjusted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Adjustment {
+    /// Adjust for the splits only
+    SplitOnly,
+    /// Adjust for the splits and the cash dividends, like the `adjclose` of yahoo! finance
+    SplitAndDividend,
+    /// Adjust for the splits, the dividends and the capital gains distributed by the fund,
+    /// as if all the distributions were reinvested
+    TotalReturn,
+}
+
+/// A price factor applying to all the quotes before the date of the event.
+struct Factor {
+    date: u64,
+    factor: f64,
+}
+
+impl YResponse {
+    /// Return the quotes back-adjusted for the events in the considered time period.
+    ///
+    /// The latest quotes are left as they are, while the open, high, low and close of the quotes
+    /// before an event are scaled so that the series doesn't jump at the event. The volumes are
+    /// adjusted for the splits only. The `adjclose` supplied by yahoo! finance is kept unchanged.
+    ///
+    /// A split of `numerator`:`denominator` turns `denominator` shares into `numerator` shares.
+    /// A distribution scales the earlier prices by `1 - amount / close`, with `close` being the
+    /// split-adjusted close of the last quote before the ex-date. The amounts reported by
+    /// yahoo! finance are already split-adjusted.
+    pub fn adjusted_quotes(&self, adjustment: Adjustment) -> Result<Vec<Quote>, YahooError> {
+        let mut quotes = self.quotes()?;
+
+        let splits: Vec<Factor> = self
+            .splits()?
+            .into_iter()
+            .filter(|s| s.numerator > 0.0 && s.denominator > 0.0)
+            .map(|s| Factor {
+                date: s.date,
+                factor: s.denominator / s.numerator,
+            })
+            .collect();
+        for quote in quotes.iter_mut() {
+            let factor = cumulative_factor(&splits, quote.timestamp);
+            quote.open *= factor;
+            quote.high *= factor;
+            quote.low *= factor;
+            quote.close *= factor;
+            quote.volume = (quote.volume as f64 / factor).round() as u64;
+        }
+
+        let mut distributions: Vec<(u64, f64)> = vec![];
+        if adjustment != Adjustment::SplitOnly {
+            distributions.extend(self.dividends()?.into_iter().map(|d| (d.date, d.amount)));
+        }
+        if adjustment == Adjustment::TotalReturn {
+            distributions.extend(
+                self.capital_gains()?
+                    .into_iter()
+                    .map(|c| (c.date, c.amount)),
+            );
+        }
+        let distributions: Vec<Factor> = distributions
+            .into_iter()
+            .filter_map(|(date, amount)| {
+                let previous = quotes.iter().rev().find(|q| q.timestamp < date)?;
+                let factor = 1.0 - amount / previous.close;
+                if factor > 0.0 && factor.is_finite() {
+                    Some(Factor { date, factor })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for quote in quotes.iter_mut() {
+            let factor = cumulative_factor(&distributions, quote.timestamp);
+            quote.open *= factor;
+            quote.high *= factor;
+            quote.low *= factor;
+            quote.close *= factor;
+        }
+
+        Ok(quotes)
+    }
+}
+
+/// The product of the factors of all the events after the timestamp.
+fn cumulative_factor(factors: &[Factor], timestamp: u64) -> f64 {
+    factors
+        .iter()
+        .filter(|f| f.date > timestamp)
+        .map(|f| f.factor)
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // five daily quotes with a 2:1 split on the third day, a dividend on the fourth
+    // and a capital gain on the fifth day
+    const CHART: &str = r#"
+    {
+        "chart": {
+            "result": [
+                {
+                    "meta": {
+                        "currency": "USD",
+                        "symbol": "FUND",
+                        "exchangeName": "NAS",
+                        "instrumentType": "MUTUALFUND",
+                        "regularMarketTime": 1704499200,
+                        "gmtoffset": -18000,
+                        "timezone": "EST",
+                        "exchangeTimezoneName": "America/New_York",
+                        "regularMarketPrice": 48.0,
+                        "chartPreviousClose": 100.0,
+                        "priceHint": 2,
+                        "currentTradingPeriod": {
+                            "pre": {"timezone": "EST", "start": 1704445200, "end": 1704465000, "gmtoffset": -18000},
+                            "regular": {"timezone": "EST", "start": 1704465000, "end": 1704488400, "gmtoffset": -18000},
+                            "post": {"timezone": "EST", "start": 1704488400, "end": 1704502800, "gmtoffset": -18000}
+                        },
+                        "dataGranularity": "1d",
+                        "range": "",
+                        "validRanges": ["1d", "5d"]
+                    },
+                    "timestamp": [1704153600, 1704240000, 1704326400, 1704412800, 1704499200],
+                    "events": {
+                        "splits": {
+                            "1704326400": {"date": 1704326400, "numerator": 2, "denominator": 1, "splitRatio": "2:1"}
+                        },
+                        "dividends": {
+                            "1704412800": {"amount": 1.0, "date": 1704412800}
+                        },
+                        "capitalGains": {
+                            "1704499200": {"amount": 2.5, "date": 1704499200}
+                        }
+                    },
+                    "indicators": {
+                        "quote": [
+                            {
+                                "volume": [1000, 1000, 2000, 2000, 2000],
+                                "high": [102.0, 104.0, 52.0, 51.0, 49.0],
+                                "close": [100.0, 100.0, 50.0, 50.0, 48.0],
+                                "low": [98.0, 96.0, 48.0, 49.0, 47.0],
+                                "open": [100.0, 100.0, 50.0, 50.0, 48.0]
+                            }
+                        ],
+                        "adjclose": [
+                            {"adjclose": [49.0, 49.0, 49.0, 50.0, 48.0]}
+                        ]
+                    }
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    fn closes(quotes: &[Quote]) -> Vec<f64> {
+        quotes
+            .iter()
+            .map(|q| (q.close * 1e6).round() / 1e6)
+            .collect()
+    }
+
+    #[test]
+    fn test_split_only() {
+        let response: YResponse = serde_json::from_str(CHART).unwrap();
+        let quotes = response.adjusted_quotes(Adjustment::SplitOnly).unwrap();
+
+        assert_eq!(closes(&quotes), vec![50.0, 50.0, 50.0, 50.0, 48.0]);
+        assert_eq!(quotes[0].high, 51.0);
+        assert_eq!(quotes[1].low, 48.0);
+        assert_eq!(
+            quotes.iter().map(|q| q.volume).collect::<Vec<_>>(),
+            vec![2000, 2000, 2000, 2000, 2000]
+        );
+        // yahoo!'s adjusted close is kept
+        assert_eq!(quotes[0].adjclose, 49.0);
+    }
+
+    #[test]
+    fn test_split_and_dividend() {
+        let response: YResponse = serde_json::from_str(CHART).unwrap();
+        let quotes = response
+            .adjusted_quotes(Adjustment::SplitAndDividend)
+            .unwrap();
+
+        // the dividend of 1.0 on a close of 50.0 scales the earlier prices by 0.98
+        assert_eq!(closes(&quotes), vec![49.0, 49.0, 49.0, 50.0, 48.0]);
+        assert_eq!(quotes[0].open, 49.0);
+        assert_eq!(quotes[3].high, 51.0);
+        assert_eq!(quotes[0].volume, 2000);
+    }
+
+    #[test]
+    fn test_total_return() {
+        let response: YResponse = serde_json::from_str(CHART).unwrap();
+        let quotes = response.adjusted_quotes(Adjustment::TotalReturn).unwrap();
+
+        // the capital gain of 2.5 on a close of 50.0 scales the earlier prices by 0.95
+        assert_eq!(closes(&quotes), vec![46.55, 46.55, 46.55, 47.5, 48.0]);
+        assert_eq!(quotes[4].open, 48.0);
+    }
+}
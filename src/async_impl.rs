@@ -1,17 +1,22 @@
+use std::collections::HashMap;
 use std::convert::TryFrom as _;
 
 use super::*;
+use wreq::header::CONTENT_TYPE;
 use wreq::StatusCode;
 use wreq::Uri;
 
+#[maybe_async::maybe_async]
 impl YahooConnector {
-    /// Retrieve the quotes of the last day for the given ticker
+    /// Retrieve the latest quotes for the given ticker, of the last month or of the longest
+    /// range yahoo! finance provides the quotes with the interval for
     pub async fn get_latest_quotes(
         &self,
         ticker: &str,
-        interval: &str,
+        interval: Interval,
     ) -> Result<YResponse, YahooError> {
-        self.get_quote_range(ticker, interval, "1mo").await
+        self.get_quote_range(ticker, interval, interval.latest_range())
+            .await
     }
 
     /// Retrieve the quote history for the given ticker form date start to end (inclusive), if available
@@ -21,7 +26,7 @@ impl YahooConnector {
         start: OffsetDateTime,
         end: OffsetDateTime,
     ) -> Result<YResponse, YahooError> {
-        self.get_quote_history_interval(ticker, start, end, "1d")
+        self.get_quote_history_interval(ticker, start, end, Interval::OneDay)
             .await
     }
 
@@ -29,12 +34,13 @@ impl YahooConnector {
     pub async fn get_quote_range(
         &self,
         ticker: &str,
-        interval: &str,
-        range: &str,
+        interval: Interval,
+        range: Range,
     ) -> Result<YResponse, YahooError> {
+        interval.check_range(range)?;
         let url: String = format!(
             YCHART_RANGE_QUERY!(),
-            url = self.url,
+            url = self.urls.chart,
             symbol = ticker,
             interval = interval,
             range = range
@@ -43,35 +49,46 @@ impl YahooConnector {
     }
 
     /// Retrieve the quote history for the given ticker form date start to end (inclusive), if available; specifying the interval of the ticker.
+    ///
+    /// Intraday periods longer than yahoo! finance returns at once are retrieved in multiple
+    /// requests, which are merged into one response.
     pub async fn get_quote_history_interval(
         &self,
         ticker: &str,
         start: OffsetDateTime,
         end: OffsetDateTime,
-        interval: &str,
+        interval: Interval,
     ) -> Result<YResponse, YahooError> {
-        let url = format!(
-            YCHART_PERIOD_QUERY!(),
-            url = self.url,
-            symbol = ticker,
-            start = start.unix_timestamp(),
-            end = end.unix_timestamp(),
-            interval = interval,
-        );
-        YResponse::from_json(self.send_request(&url).await?)
+        interval.check_period(start, end)?;
+        let windows = interval.windows(start, end);
+        if windows.len() == 1 {
+            return self
+                .get_quote_history_window(ticker, start, end, interval)
+                .await;
+        }
+
+        let mut responses = Vec::with_capacity(windows.len());
+        for (window_start, window_end) in windows {
+            responses.push(
+                self.get_quote_history_window(ticker, window_start, window_end, interval)
+                    .await?,
+            );
+        }
+        YResponse::merge(responses)
     }
 
     /// Retrieve the quote history for the given ticker for a given period and ticker interval and optionally before and after regular trading hours
     pub async fn get_quote_period_interval(
         &self,
         ticker: &str,
-        period: &str,
-        interval: &str,
+        period: Range,
+        interval: Interval,
         prepost: bool,
     ) -> Result<YResponse, YahooError> {
+        interval.check_range(period)?;
         let url = format!(
             YCHART_PERIOD_INTERVAL_QUERY!(),
-            url = self.url,
+            url = self.urls.chart,
             symbol = ticker,
             period = period,
             interval = interval,
@@ -82,7 +99,7 @@ impl YahooConnector {
 
     /// Retrieve the list of quotes found searching a given name
     pub async fn search_ticker_opt(&self, name: &str) -> Result<YSearchResultOpt, YahooError> {
-        let url = format!(YTICKER_QUERY!(), url = self.search_url, name = name);
+        let url = format!(YTICKER_QUERY!(), url = self.urls.search, name = name);
         YSearchResultOpt::from_json(self.send_request(&url).await?)
     }
 
@@ -92,11 +109,11 @@ impl YahooConnector {
         Ok(YSearchResult::from_opt(&result))
     }
 
-    /// Get list for options for a given name
+    /// Get the list of options of the nearest expiration date for a given name
     pub async fn search_options(&self, name: &str) -> Result<YOptionResults, YahooError> {
-        let url = format!("https://finance.yahoo.com/quote/{name}/options?p={name}");
-        let resp = self.send_request_raw(&url).await?;
-        Ok(YOptionResults::scrape(&resp))
+        let url = options::compose_options_url(&self.urls.options, name);
+        let resp = self.send_request(&url).await?;
+        YOptionResults::from_chain(&options::option_chain_from_response(resp)?)
     }
 
     pub async fn get_income_statement(
@@ -106,7 +123,13 @@ impl YahooConnector {
         until: OffsetDateTime,
         facts: &[fundamentals::IncomeStatementFact],
     ) -> Result<fundamentals::IncomeStatement, YahooError> {
-        let url = fundamentals::compose_fundamentals_url(name, period.clone(), until, facts);
+        let url = fundamentals::compose_fundamentals_url(
+            &self.urls.fundamentals,
+            name,
+            period.clone(),
+            until,
+            facts,
+        );
         let resp = self.send_request(&url).await?;
         fundamentals::from_response(resp, period, facts)
     }
@@ -118,7 +141,13 @@ impl YahooConnector {
         until: OffsetDateTime,
         facts: &[fundamentals::BalanceSheetFact],
     ) -> Result<fundamentals::BalanceSheet, YahooError> {
-        let url = fundamentals::compose_fundamentals_url(name, period.clone(), until, facts);
+        let url = fundamentals::compose_fundamentals_url(
+            &self.urls.fundamentals,
+            name,
+            period.clone(),
+            until,
+            facts,
+        );
         let resp = self.send_request(&url).await?;
         fundamentals::from_response(resp, period, facts)
     }
@@ -130,7 +159,13 @@ impl YahooConnector {
         until: OffsetDateTime,
         facts: &[fundamentals::CashflowFact],
     ) -> Result<fundamentals::Cashflow, YahooError> {
-        let url = fundamentals::compose_fundamentals_url(name, period.clone(), until, facts);
+        let url = fundamentals::compose_fundamentals_url(
+            &self.urls.fundamentals,
+            name,
+            period.clone(),
+            until,
+            facts,
+        );
         let resp = self.send_request(&url).await?;
         fundamentals::from_response(resp, period, facts)
     }
@@ -140,14 +175,163 @@ impl YahooConnector {
         name: &str,
         fields: &[quote_summary::QuoteSummaryField],
     ) -> Result<quote_summary::QuoteSummary, YahooError> {
-        let url = quote_summary::compose_url(name, fields);
+        let url = quote_summary::compose_url(&self.urls.quote_summary, name, fields);
         let resp = self.send_request(&url).await?;
         let ret = quote_summary::from_response(resp);
         ret
     }
 
+    /// Retrieve the profile, the top holdings and the trailing returns of a fund or an ETF
+    ///
+    /// Fails with `YahooError::EmptyDataSet` if yahoo! finance has no fund data for the symbol.
+    pub async fn get_fund_info(&self, name: &str) -> Result<FundInfo, YahooError> {
+        let summary = self
+            .get_quote_summary(
+                name,
+                &[
+                    quote_summary::QuoteSummaryField::TopHoldings,
+                    quote_summary::QuoteSummaryField::FundProfile,
+                    quote_summary::QuoteSummaryField::FundPerformance,
+                ],
+            )
+            .await?;
+        FundInfo::from_summary(summary)
+    }
+
+    /// Retrieve the recommendation trend and the rating changes of the analysts for a symbol
+    pub async fn get_recommendations(&self, name: &str) -> Result<Recommendations, YahooError> {
+        let summary = self
+            .get_quote_summary(
+                name,
+                &[
+                    quote_summary::QuoteSummaryField::RecommendationTrend,
+                    quote_summary::QuoteSummaryField::UpgradeDowngradeHistory,
+                ],
+            )
+            .await?;
+        Recommendations::from_summary(summary)
+    }
+
+    /// Retrieve the major holders, the institutional and fund holders, the insiders
+    /// and their trades of a symbol
+    pub async fn get_ownership(&self, name: &str) -> Result<OwnershipInfo, YahooError> {
+        let summary = self
+            .get_quote_summary(
+                name,
+                &[
+                    quote_summary::QuoteSummaryField::MajorHoldersBreakdown,
+                    quote_summary::QuoteSummaryField::InstitutionOwnership,
+                    quote_summary::QuoteSummaryField::FundOwnership,
+                    quote_summary::QuoteSummaryField::InsiderHolders,
+                    quote_summary::QuoteSummaryField::InsiderTransactions,
+                    quote_summary::QuoteSummaryField::NetSharePurchaseActivity,
+                ],
+            )
+            .await?;
+        OwnershipInfo::from_summary(summary)
+    }
+
+    /// Retrieve the latest ESG scores of a symbol, with the involvement in controversial products
+    pub async fn get_esg_scores(&self, name: &str) -> Result<quote_summary::EsgScores, YahooError> {
+        let summary = self
+            .get_quote_summary(name, &[quote_summary::QuoteSummaryField::EsgScores])
+            .await?;
+        summary.esg_scores.ok_or(YahooError::EmptyDataSet)
+    }
+
+    /// Retrieve the history of the ESG scores of a symbol and of its peer group
+    pub async fn get_esg_history(&self, name: &str) -> Result<EsgHistory, YahooError> {
+        let url = esg::compose_url(&self.urls.esg_chart, name);
+        let resp = self.send_request(&url).await?;
+        esg::from_response(resp)
+    }
+
+    /// Retrieve the recent SEC filings of a symbol selected by the filter, latest first
+    pub async fn get_sec_filings(
+        &self,
+        name: &str,
+        filter: &FilingFilter,
+    ) -> Result<Vec<Filing>, YahooError> {
+        let summary = self
+            .get_quote_summary(name, &[quote_summary::QuoteSummaryField::SecFilings])
+            .await?;
+        let mut filings = Filing::from_summary(summary)?;
+        filings.retain(|f| filter.matches(f));
+        Ok(filings)
+    }
+
+    /// Retrieve the upcoming earnings and dividend dates of a symbol
+    pub async fn get_calendar_events(&self, name: &str) -> Result<CorporateEvents, YahooError> {
+        let summary = self
+            .get_quote_summary(name, &[quote_summary::QuoteSummaryField::CalendarEvents])
+            .await?;
+        CorporateEvents::from_summary(name, summary)
+    }
+
+    /// Retrieve the events of the symbols taking place from today up to `days` days from now
+    ///
+    /// The calendars of the symbols are retrieved like in [`YahooConnector::get_calendar_events_many`].
+    /// An `InvalidParameter` error is returned if the last day is out of the range of dates.
+    pub async fn get_upcoming_events(
+        &self,
+        symbols: &[&str],
+        days: u32,
+    ) -> Result<UpcomingEvents, YahooError> {
+        let today = OffsetDateTime::now_utc().date();
+        let last = today
+            .checked_add(time::Duration::days(days.into()))
+            .ok_or_else(|| {
+                YahooError::InvalidParameter(format!("{} days from today are too late", days))
+            })?;
+        let results = self.get_calendar_events_many(symbols).await;
+        Ok(UpcomingEvents::collect(results, today, last))
+    }
+
+    /// Retrieve the earnings released from the `first` to the `last` date, both included,
+    /// from the earnings calendar of yahoo! finance
+    ///
+    /// The earnings of all the companies are returned if no symbols are given. The pages of the
+    /// calendar are requested until the total number of earnings is reached.
+    pub async fn get_earnings_calendar(
+        &self,
+        first: time::Date,
+        last: time::Date,
+        symbols: &[&str],
+    ) -> Result<Vec<EarningsCalendarEntry>, YahooError> {
+        let url = calendar::compose_url(&self.urls.visualization);
+        let mut entries = vec![];
+        for _ in 0..calendar::EARNINGS_MAX_PAGES {
+            let query = calendar::earnings_query(first, last, symbols, entries.len())?;
+            let resp = self.post_request(&url, &query).await?;
+            let page = calendar::earnings_from_response(resp)?;
+            let received = page.entries.len();
+            entries.extend(page.entries);
+            if received < calendar::EARNINGS_PAGE_SIZE
+                || page.total.is_some_and(|total| entries.len() >= total)
+            {
+                return Ok(entries);
+            }
+        }
+        Err(YahooError::FetchFailed(format!(
+            "the earnings calendar returned more than {} pages",
+            calendar::EARNINGS_MAX_PAGES
+        )))
+    }
+
+    /// Retrieve the current quotes of multiple symbols in one request
+    ///
+    /// Symbols unknown to yahoo! finance are missing in the result.
+    pub async fn get_quotes(
+        &self,
+        symbols: &[&str],
+    ) -> Result<HashMap<String, QuoteSnapshot>, YahooError> {
+        let url = quote_snapshot::compose_url(&self.urls.quote, symbols);
+        let resp = self.send_request(&url).await?;
+        quote_snapshot::from_response(resp)
+    }
+
     pub async fn get_options(&self, name: &str) -> Result<options::Options, YahooError> {
-        let url = options::compose_options_url(name);
+        let url = options::compose_options_url(&self.urls.options, name);
         let resp = self.send_request(&url).await?;
         options::options_from_response(resp)
     }
@@ -157,38 +341,308 @@ impl YahooConnector {
         name: &str,
         expiration_date: OffsetDateTime,
     ) -> Result<options::OptionChain, YahooError> {
-        let url = options::compose_option_chain_url(name, expiration_date);
+        let url = options::compose_option_chain_url(
+            &self.urls.options,
+            name,
+            expiration_date,
+            &options::OptionChainQuery::new(),
+        );
         let resp = self.send_request(&url).await?;
         options::option_chain_from_response(resp)
     }
 
-    /// Send request to yahoo! finance server and transform response to JSON value
-    async fn send_request_raw(&self, url: &str) -> Result<String, YahooError> {
-        let mut url = Uri::try_from(url)
-            .map_err(|e| YahooError::FetchFailed(format!("failed to parse the URL: {}", e)))?;
+    /// Retrieve the option chains of all the expirations of a symbol, sending at most
+    /// `max_concurrency` requests (see [`YahooConnectorBuilder::max_concurrency`]) at once.
+    ///
+    /// Fails if any of the option chains can't be retrieved.
+    pub async fn get_option_surface(
+        &self,
+        name: &str,
+    ) -> Result<options::OptionSurface, YahooError> {
+        let options = self.get_options(name).await?;
+        let chains = self
+            .get_option_chains_of(
+                name,
+                &options.expiration_dates,
+                &options::OptionChainQuery::new(),
+            )
+            .await?;
+        Ok(options::OptionSurface::new(name, options.quote, chains))
+    }
 
-        self.crumb.enrich(&mut url).await?;
+    /// Retrieve the contracts of a symbol selected by the query, ordered by expiration date.
+    ///
+    /// Only the expirations selected by the query are retrieved, at most `max_concurrency`
+    /// (see [`YahooConnectorBuilder::max_concurrency`]) at once, and yahoo! finance is asked
+    /// for the strikes within the moneyness band only. The moneyness band is centered on the
+    /// market price of the symbol unless the query has a spot price.
+    pub async fn get_option_chains(
+        &self,
+        name: &str,
+        query: &options::OptionChainQuery,
+    ) -> Result<Vec<options::OptionChain>, YahooError> {
+        let options = self.get_options(name).await?;
+        let mut query = query.clone();
+        if query.needs_spot() {
+            let spot = options
+                .quote
+                .as_ref()
+                .and_then(|quote| quote.regular_market_price)
+                .ok_or_else(|| {
+                    YahooError::FetchFailed(format!("the market price of {} is missing", name))
+                })?;
+            query = query.spot(spot);
+        }
+        let dates: Vec<i64> = options
+            .expiration_dates
+            .into_iter()
+            .filter(|date| query.matches_expiry(*date))
+            .collect();
+        let mut chains = self.get_option_chains_of(name, &dates, &query).await?;
+        chains.sort_by_key(|c| c.expiration_date);
+        Ok(chains.iter().map(|c| query.apply(c)).collect())
+    }
 
-        let resp = self
-            .client
-            .get(url.clone())
-            .send()
+    #[maybe_async::async_impl]
+    async fn get_option_chains_of(
+        &self,
+        name: &str,
+        dates: &[i64],
+        query: &options::OptionChainQuery,
+    ) -> Result<Vec<options::OptionChain>, YahooError> {
+        use futures::stream::{self, StreamExt};
+
+        let chains: Vec<Result<options::OptionChain, YahooError>> = stream::iter(dates)
+            .map(|date| self.get_option_chain_of(name, *date, query))
+            .buffer_unordered(self.max_concurrency)
+            .collect()
+            .await;
+        chains.into_iter().collect()
+    }
+
+    #[maybe_async::sync_impl]
+    fn get_option_chains_of(
+        &self,
+        name: &str,
+        dates: &[i64],
+        query: &options::OptionChainQuery,
+    ) -> Result<Vec<options::OptionChain>, YahooError> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Mutex;
+
+        let next = AtomicUsize::new(0);
+        let chains = Mutex::new(Vec::with_capacity(dates.len()));
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.max_concurrency.min(dates.len()) {
+                scope.spawn(|| {
+                    while let Some(date) = dates.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let res = self.get_option_chain_of(name, *date, query);
+                        chains.lock().unwrap().push(res);
+                    }
+                });
+            }
+        });
+
+        chains.into_inner().unwrap().into_iter().collect()
+    }
+
+    async fn get_option_chain_of(
+        &self,
+        name: &str,
+        date: i64,
+        query: &options::OptionChainQuery,
+    ) -> Result<options::OptionChain, YahooError> {
+        let date =
+            OffsetDateTime::from_unix_timestamp(date).map_err(|_| YahooError::DataInconsistency)?;
+        let url = options::compose_option_chain_url(&self.urls.options, name, date, query);
+        let resp = self.send_request(&url).await?;
+        options::option_chain_from_response(resp)
+    }
+
+    async fn get_quote_history_window(
+        &self,
+        ticker: &str,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        interval: Interval,
+    ) -> Result<YResponse, YahooError> {
+        let url = format!(
+            YCHART_PERIOD_QUERY!(),
+            url = self.urls.chart,
+            symbol = ticker,
+            start = start.unix_timestamp(),
+            end = end.unix_timestamp(),
+            interval = interval,
+        );
+        YResponse::from_json(self.send_request(&url).await?)
+    }
+
+    async fn send_request(&self, url: &str) -> Result<serde_json::Value, YahooError> {
+        Ok(serde_json::from_str(&self.send_request_raw(url).await?)?)
+    }
+
+    async fn post_request(
+        &self,
+        url: &str,
+        payload: &serde_json::Value,
+    ) -> Result<serde_json::Value, YahooError> {
+        let resp = self.post_request_raw(url, &payload.to_string()).await?;
+        Ok(serde_json::from_str(&resp)?)
+    }
+}
+
+impl YahooConnector {
+    /// Retrieve the quote histories of multiple tickers, sending at most `max_concurrency`
+    /// requests (see [`YahooConnectorBuilder::max_concurrency`]) at once.
+    ///
+    /// A failure to retrieve one of the tickers doesn't affect the others, the result
+    /// of each ticker is returned separately.
+    #[maybe_async::async_impl]
+    pub async fn get_quote_history_many(
+        &self,
+        tickers: &[&str],
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        interval: Interval,
+    ) -> HashMap<String, Result<YResponse, YahooError>> {
+        use futures::stream::{self, StreamExt};
+
+        stream::iter(tickers)
+            .map(|ticker| async move {
+                let res = self
+                    .get_quote_history_interval(ticker, start, end, interval)
+                    .await;
+                (ticker.to_string(), res)
+            })
+            .buffer_unordered(self.max_concurrency)
+            .collect()
             .await
-            .map_err(|e| YahooError::from_wreq_while(e, "getting the data"))?;
+    }
+
+    /// Retrieve the quote histories of multiple tickers, sending at most `max_concurrency`
+    /// requests (see [`YahooConnectorBuilder::max_concurrency`]) at once.
+    ///
+    /// A failure to retrieve one of the tickers doesn't affect the others, the result
+    /// of each ticker is returned separately.
+    #[maybe_async::sync_impl]
+    pub fn get_quote_history_many(
+        &self,
+        tickers: &[&str],
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        interval: Interval,
+    ) -> HashMap<String, Result<YResponse, YahooError>> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Mutex;
 
-        let status = resp.status();
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(HashMap::new());
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.max_concurrency.min(tickers.len()) {
+                scope.spawn(|| {
+                    while let Some(ticker) = tickers.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let res = self.get_quote_history_interval(ticker, start, end, interval);
+                        results.lock().unwrap().insert(ticker.to_string(), res);
+                    }
+                });
+            }
+        });
 
-        let body = resp
-            .text()
+        results.into_inner().unwrap()
+    }
+
+    /// Retrieve the calendars of multiple symbols, sending at most `max_concurrency`
+    /// requests (see [`YahooConnectorBuilder::max_concurrency`]) at once.
+    ///
+    /// A failure to retrieve one of the symbols doesn't affect the others.
+    #[maybe_async::async_impl]
+    pub async fn get_calendar_events_many(
+        &self,
+        symbols: &[&str],
+    ) -> HashMap<String, Result<CorporateEvents, YahooError>> {
+        use futures::stream::{self, StreamExt};
+
+        stream::iter(symbols)
+            .map(|symbol| async move { (symbol.to_string(), self.get_calendar_events(symbol).await) })
+            .buffer_unordered(self.max_concurrency)
+            .collect()
             .await
-            .map_err(|e| YahooError::from_wreq_while(e, "reading the response"))?;
+    }
 
-        log::trace!(
-            "Yahoo URL {} response status {}, body: {}",
-            url,
-            status,
-            body
-        );
+    /// Retrieve the calendars of multiple symbols, sending at most `max_concurrency`
+    /// requests (see [`YahooConnectorBuilder::max_concurrency`]) at once.
+    ///
+    /// A failure to retrieve one of the symbols doesn't affect the others.
+    #[maybe_async::sync_impl]
+    pub fn get_calendar_events_many(
+        &self,
+        symbols: &[&str],
+    ) -> HashMap<String, Result<CorporateEvents, YahooError>> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Mutex;
+
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(HashMap::new());
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.max_concurrency.min(symbols.len()) {
+                scope.spawn(|| {
+                    while let Some(symbol) = symbols.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let res = self.get_calendar_events(symbol);
+                        results.lock().unwrap().insert(symbol.to_string(), res);
+                    }
+                });
+            }
+        });
+
+        results.into_inner().unwrap()
+    }
+}
+
+impl YahooConnector {
+    #[maybe_async::async_impl]
+    async fn send_request_raw(&self, url: &str) -> Result<String, YahooError> {
+        self.fetch(url, None).await
+    }
+
+    #[maybe_async::sync_impl]
+    fn send_request_raw(&self, url: &str) -> Result<String, YahooError> {
+        self.runtime.block_on(self.fetch(url, None))
+    }
+
+    #[maybe_async::async_impl]
+    async fn post_request_raw(&self, url: &str, payload: &str) -> Result<String, YahooError> {
+        self.fetch(url, Some(payload)).await
+    }
+
+    #[maybe_async::sync_impl]
+    fn post_request_raw(&self, url: &str, payload: &str) -> Result<String, YahooError> {
+        self.runtime.block_on(self.fetch(url, Some(payload)))
+    }
+
+    /// Send request to yahoo! finance server and return the body of the response.
+    ///
+    /// The request is a POST of the JSON payload if there is one, a GET otherwise.
+    /// This is always async. In the blocking mode, it is driven by the connector's own runtime.
+    async fn fetch(&self, url: &str, payload: Option<&str>) -> Result<String, YahooError> {
+        let (status, body) = match &self.cassette {
+            Some(cassette) if cassette.is_replaying() => {
+                let (status, body) = cassette.play(url, payload)?;
+                let status = StatusCode::from_u16(status).map_err(|e| {
+                    YahooError::CassetteFailed(format!("invalid recorded status: {}", e))
+                })?;
+                (status, body)
+            }
+            cassette => {
+                let (status, body) = self.fetch_from_server(url, payload).await?;
+                if let Some(cassette) = cassette {
+                    cassette.save(url, payload, status.as_u16(), &body)?;
+                }
+                (status, body)
+            }
+        };
 
         match status {
             StatusCode::OK => Ok(body),
@@ -199,8 +653,63 @@ impl YahooConnector {
         }
     }
 
-    async fn send_request(&self, url: &str) -> Result<serde_json::Value, YahooError> {
-        Ok(serde_json::from_str(&self.send_request_raw(url).await?)?)
+    async fn fetch_from_server(
+        &self,
+        url: &str,
+        payload: Option<&str>,
+    ) -> Result<(StatusCode, String), YahooError> {
+        let mut url = Uri::try_from(url)
+            .map_err(|e| YahooError::FetchFailed(format!("failed to parse the URL: {}", e)))?;
+
+        self.crumb.enrich(&mut url).await?;
+
+        retry::retry(self.retry_policy.as_ref(), || async {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+
+            let request = match payload {
+                Some(payload) => self
+                    .client
+                    .post(url.clone())
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(payload.to_string()),
+                None => self.client.get(url.clone()),
+            };
+            let resp = request
+                .send()
+                .await
+                .map_err(|e| YahooError::from_wreq_while(e, "getting the data"))?;
+
+            let status = resp.status();
+            let retry_after = retry::retry_after(resp.headers());
+
+            let body = resp
+                .text()
+                .await
+                .map_err(|e| YahooError::from_wreq_while(e, "reading the response"))?;
+
+            log::trace!(
+                "Yahoo URL {} response status {}, body: {}",
+                url,
+                status,
+                body
+            );
+
+            if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                return Err(retry::Failure {
+                    error: YahooError::FetchFailed(format!(
+                        "status {}, response: {}",
+                        status, body
+                    )),
+                    retryable: true,
+                    retry_after,
+                });
+            }
+
+            Ok((status, body))
+        })
+        .await
     }
 }
 
@@ -209,25 +718,451 @@ mod tests {
     use time::macros::datetime;
 
     use super::*;
+    use crate::cassette::with_cassette;
+
+    fn chart_json(symbol: &str, quotes: &[(u64, f64)]) -> String {
+        let list = |values: Vec<String>| values.join(", ");
+        let close = quotes[quotes.len() - 1].1;
+        format!(
+            r#"{{"chart": {{"result": [{{
+                "meta": {{
+                    "currency": "USD", "symbol": "{symbol}", "exchangeName": "NMS",
+                    "instrumentType": "EQUITY", "regularMarketTime": 1580504400,
+                    "gmtoffset": -18000, "timezone": "EST",
+                    "exchangeTimezoneName": "America/New_York", "regularMarketPrice": {close},
+                    "chartPreviousClose": {close}, "priceHint": 2,
+                    "currentTradingPeriod": {{
+                        "pre": {{"timezone": "EST", "start": 1580461200, "end": 1580481000, "gmtoffset": -18000}},
+                        "regular": {{"timezone": "EST", "start": 1580481000, "end": 1580504400, "gmtoffset": -18000}},
+                        "post": {{"timezone": "EST", "start": 1580504400, "end": 1580518800, "gmtoffset": -18000}}
+                    }},
+                    "dataGranularity": "1d", "range": "", "validRanges": ["1d", "5d"]
+                }},
+                "timestamp": [{timestamps}],
+                "indicators": {{
+                    "quote": [{{"volume": [{volumes}], "high": [{closes}], "close": [{closes}], "low": [{closes}], "open": [{closes}]}}],
+                    "adjclose": [{{"adjclose": [{closes}]}}]
+                }}
+            }}], "error": null}}}}"#,
+            symbol = symbol,
+            close = close,
+            timestamps = list(quotes.iter().map(|q| q.0.to_string()).collect()),
+            volumes = list(quotes.iter().map(|_| "100".to_string()).collect()),
+            closes = list(quotes.iter().map(|q| q.1.to_string()).collect()),
+        )
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_quote_history_many() {
+        let start = datetime!(2020-01-31 0:00:00.00 UTC);
+        let end = datetime!(2020-01-31 23:59:59.00 UTC);
+        let url = |symbol: &str| {
+            format!(
+                "{}/{symbol}?symbol={symbol}&period1={}&period2={}&interval=1d&events=div|split|capitalGains",
+                YCHART_URL,
+                start.unix_timestamp(),
+                end.unix_timestamp(),
+                symbol = symbol
+            )
+        };
+
+        let cassette = with_cassette("history_many", |recorder| {
+            recorder
+                .save(
+                    &url("AAPL"),
+                    None,
+                    200,
+                    &chart_json("AAPL", &[(1580481000, 77.38)]),
+                )
+                .unwrap();
+            recorder
+                .save(
+                    &url("IBM"),
+                    None,
+                    200,
+                    &chart_json("IBM", &[(1580481000, 143.73)]),
+                )
+                .unwrap();
+            recorder
+                .save(
+                    &url("GONE"),
+                    None,
+                    404,
+                    r#"{"chart": {"result": null, "error": {"code": "Not Found"}}}"#,
+                )
+                .unwrap();
+        });
+
+        let provider = YahooConnector::builder()
+            .cassette(cassette)
+            .max_concurrency(2)
+            .build()
+            .unwrap();
+
+        let res = provider
+            .get_quote_history_many(&["AAPL", "IBM", "GONE"], start, end, Interval::OneDay)
+            .await;
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(
+            res["AAPL"].as_ref().unwrap().last_quote().unwrap().close,
+            77.38
+        );
+        assert_eq!(
+            res["IBM"].as_ref().unwrap().last_quote().unwrap().close,
+            143.73
+        );
+        assert!(matches!(res["GONE"], Err(YahooError::FetchFailed(_))));
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_option_surface_and_chains() {
+        let contract = |symbol: &str, strike: f64, expiration: i64| {
+            format!(
+                r#"{{"contractSymbol": "{symbol}", "strike": {strike}, "currency": "USD",
+                    "lastPrice": 1.5, "change": 0.0, "percentChange": 0.0, "openInterest": 10,
+                    "bid": 1.4, "ask": 1.6, "contractSize": "REGULAR", "expiration": {expiration},
+                    "lastTradeDate": 1732299649, "impliedVolatility": 0.25, "inTheMoney": false}}"#,
+                symbol = symbol,
+                strike = strike,
+                expiration = expiration
+            )
+        };
+        let response = |options: &str| {
+            format!(
+                r#"{{"optionChain": {{"result": [{{
+                    "underlyingSymbol": "AAPL", "expirationDates": [1733443200, 1734048000],
+                    "strikes": [230.0], "hasMiniOptions": false,
+                    "quote": {{"symbol": "AAPL", "regularMarketPrice": 229.87}},
+                    "options": [{}]
+                }}], "error": null}}}}"#,
+                options
+            )
+        };
+        let chain = |expiration: i64, calls: &str, puts: &str| {
+            response(&format!(
+                r#"{{"expirationDate": {}, "hasMiniOptions": false, "calls": [{}], "puts": [{}]}}"#,
+                expiration, calls, puts
+            ))
+        };
+        let url = |date: Option<i64>| match date {
+            Some(date) => format!("{}/AAPL?date={}", OPTIONS_URL, date),
+            None => format!("{}/AAPL", OPTIONS_URL),
+        };
+
+        let cassette = with_cassette("surface", |recorder| {
+            recorder
+                .save(&url(None), None, 200, &chain(1733443200, "", ""))
+                .unwrap();
+            recorder
+                .save(
+                    &url(Some(1733443200)),
+                    None,
+                    200,
+                    &chain(
+                        1733443200,
+                        &contract("AAPL241206C00230000", 230.0, 1733443200),
+                        &contract("AAPL241206P00230000", 230.0, 1733443200),
+                    ),
+                )
+                .unwrap();
+            recorder
+                .save(
+                    &url(Some(1734048000)),
+                    None,
+                    200,
+                    &chain(
+                        1734048000,
+                        &contract("AAPL241213C00230000", 230.0, 1734048000),
+                        "",
+                    ),
+                )
+                .unwrap();
+            recorder
+                .save(
+                    &format!("{}&strikeMin=150&strikeMax=250", url(Some(1734048000))),
+                    None,
+                    200,
+                    &chain(
+                        1734048000,
+                        &contract("AAPL241213C00230000", 230.0, 1734048000),
+                        "",
+                    ),
+                )
+                .unwrap();
+            // the moneyness band is centered on the market price of the underlying by default
+            recorder
+                .save(
+                    &format!(
+                        "{}&strikeMin={}&strikeMax={}&straddle=true",
+                        url(Some(1733443200)),
+                        229.87 * (1.0 - 0.1),
+                        229.87 * (1.0 + 0.1)
+                    ),
+                    None,
+                    200,
+                    &response(&format!(
+                        r#"{{"expirationDate": 1733443200, "hasMiniOptions": false,
+                            "straddles": [{{"strike": 230.0, "call": {}, "put": {}}}]}}"#,
+                        contract("AAPL241206C00230000", 230.0, 1733443200),
+                        contract("AAPL241206P00230000", 230.0, 1733443200)
+                    )),
+                )
+                .unwrap();
+        });
+
+        let provider = YahooConnector::builder()
+            .cassette(cassette)
+            .max_concurrency(2)
+            .build()
+            .unwrap();
+
+        let surface = provider.get_option_surface("AAPL").await.unwrap();
+        assert_eq!(surface.symbol, "AAPL");
+        assert_eq!(
+            surface.quote.as_ref().unwrap().regular_market_price,
+            Some(229.87)
+        );
+        assert_eq!(surface.chains().len(), 2);
+        assert_eq!(surface.chains()[0].expiration_date, 1733443200);
+        assert_eq!(surface.iter().count(), 3);
+        let put = surface
+            .get(
+                time::macros::date!(2024 - 12 - 06),
+                230.0,
+                options::OptionType::Put,
+            )
+            .unwrap();
+        assert_eq!(put.contract_symbol, "AAPL241206P00230000");
+        assert!(surface
+            .get(
+                time::macros::date!(2024 - 12 - 13),
+                230.0,
+                options::OptionType::Put
+            )
+            .is_none());
+
+        let query = options::OptionChainQuery::new()
+            .expiries(
+                time::macros::date!(2024 - 12 - 07),
+                time::macros::date!(2024 - 12 - 31),
+            )
+            .moneyness(0.25)
+            .spot(200.0);
+        let chains = provider.get_option_chains("AAPL", &query).await.unwrap();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].expiration_date, 1734048000);
+        assert_eq!(chains[0].calls[0].contract_symbol, "AAPL241213C00230000");
+
+        let query = options::OptionChainQuery::new()
+            .expiries(
+                time::macros::date!(2024 - 12 - 06),
+                time::macros::date!(2024 - 12 - 06),
+            )
+            .moneyness(0.1)
+            .straddle(true);
+        let chains = provider.get_option_chains("AAPL", &query).await.unwrap();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].calls[0].contract_symbol, "AAPL241206C00230000");
+        assert_eq!(chains[0].puts[0].contract_symbol, "AAPL241206P00230000");
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_latest_quotes_range() {
+        let url = |interval: Interval, range: Range| {
+            format!(
+                "{}/AAPL?symbol=AAPL&interval={}&range={}&events=div|split|capitalGains",
+                YCHART_URL, interval, range
+            )
+        };
+        let cassette = with_cassette("latest_quotes", |recorder| {
+            for (interval, range) in [
+                (Interval::OneMinute, Range::FiveDays),
+                (Interval::TwoMinutes, Range::OneMonth),
+            ] {
+                recorder
+                    .save(
+                        &url(interval, range),
+                        None,
+                        200,
+                        &chart_json("AAPL", &[(1580481000, 77.38)]),
+                    )
+                    .unwrap();
+            }
+        });
+        let provider = YahooConnector::builder()
+            .cassette(cassette)
+            .build()
+            .unwrap();
+
+        // one month of 1 minute quotes can't be requested at once
+        for interval in [Interval::OneMinute, Interval::TwoMinutes] {
+            let response = provider.get_latest_quotes("AAPL", interval).await.unwrap();
+            assert_eq!(response.last_quote().unwrap().close, 77.38);
+        }
 
-    #[test]
-    fn test_get_single_quote() {
+        // the range is rejected before sending a request, which is not recorded
+        let res = provider
+            .get_quote_range("AAPL", Interval::OneMinute, Range::OneMonth)
+            .await;
+        assert!(matches!(res, Err(YahooError::InvalidParameter(_))));
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_quote_history_chunked() {
+        let end = OffsetDateTime::now_utc().replace_nanosecond(0).unwrap();
+        let middle = end - time::Duration::days(2);
+        let start = middle - time::Duration::days(7);
+        let url = |start: OffsetDateTime, end: OffsetDateTime| {
+            format!(
+                "{}/AAPL?symbol=AAPL&period1={}&period2={}&interval=1m&events=div|split|capitalGains",
+                YCHART_URL,
+                start.unix_timestamp(),
+                end.unix_timestamp(),
+            )
+        };
+        let ts = |t: OffsetDateTime| t.unix_timestamp() as u64;
+
+        let cassette = with_cassette("history_chunked", |recorder| {
+            recorder
+                .save(
+                    &url(start, middle),
+                    None,
+                    200,
+                    &chart_json("AAPL", &[(ts(start), 1.0), (ts(middle), 2.0)]),
+                )
+                .unwrap();
+            recorder
+                .save(
+                    &url(middle, end),
+                    None,
+                    200,
+                    &chart_json("AAPL", &[(ts(middle), 2.5), (ts(end), 3.0)]),
+                )
+                .unwrap();
+        });
+
+        let provider = YahooConnector::builder()
+            .cassette(cassette)
+            .build()
+            .unwrap();
+
+        let response = provider
+            .get_quote_history_interval("AAPL", start, end, Interval::OneMinute)
+            .await
+            .unwrap();
+        let quotes = response.quotes().unwrap();
+        assert_eq!(
+            quotes.iter().map(|q| q.timestamp).collect::<Vec<_>>(),
+            vec![ts(start), ts(middle), ts(end)]
+        );
+        assert_eq!(
+            quotes.iter().map(|q| q.close).collect::<Vec<_>>(),
+            vec![1.0, 2.5, 3.0]
+        );
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_earnings_calendar() {
+        let first = time::macros::date!(2024 - 10 - 31);
+        let last = time::macros::date!(2024 - 11 - 01);
+        let url = format!("{}?lang=en-US&region=US", YVISUALIZATION_URL);
+        let query = calendar::earnings_query(first, last, &["AAPL"], 0)
+            .unwrap()
+            .to_string();
+
+        let cassette = with_cassette("earnings_calendar", |recorder| {
+            recorder
+                .save(
+                    &url,
+                    Some(&query),
+                    200,
+                    r#"{"finance": {"result": [{"total": 1, "documents": [{
+                        "columns": [{"id": "ticker"}, {"id": "startdatetime"}, {"id": "epsestimate"}],
+                        "rows": [["AAPL", "2024-10-31T20:30:00.000Z", 1.6]]
+                    }]}], "error": null}}"#,
+                )
+                .unwrap();
+        });
+
+        let provider = YahooConnector::builder()
+            .cassette(cassette)
+            .build()
+            .unwrap();
+
+        let entries = provider
+            .get_earnings_calendar(first, last, &["AAPL"])
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].symbol, "AAPL");
+        assert_eq!(entries[0].eps_estimate, Some(1.6));
+
+        // another query is not recorded
+        let missing = provider.get_earnings_calendar(first, last, &[]).await;
+        assert!(missing.is_err());
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_earnings_calendar_total() {
+        let first = time::macros::date!(2024 - 10 - 28);
+        let last = time::macros::date!(2024 - 11 - 01);
+        let url = format!("{}?lang=en-US&region=US", YVISUALIZATION_URL);
+        let row = r#"["AAPL", "2024-10-31T20:30:00.000Z", 1.6]"#;
+        let rows = vec![row; calendar::EARNINGS_PAGE_SIZE].join(", ");
+        let body = format!(
+            r#"{{"finance": {{"result": [{{"total": 150, "documents": [{{
+                "columns": [{{"id": "ticker"}}, {{"id": "startdatetime"}}, {{"id": "epsestimate"}}],
+                "rows": [{}]
+            }}]}}], "error": null}}}}"#,
+            rows
+        );
+
+        // only the first two pages are recorded, a third request would fail
+        let cassette = with_cassette("earnings_calendar_total", |recorder| {
+            for offset in [0, calendar::EARNINGS_PAGE_SIZE] {
+                let query = calendar::earnings_query(first, last, &[], offset)
+                    .unwrap()
+                    .to_string();
+                recorder.save(&url, Some(&query), 200, &body).unwrap();
+            }
+        });
+        let provider = YahooConnector::builder()
+            .cassette(cassette)
+            .build()
+            .unwrap();
+
+        let entries = provider
+            .get_earnings_calendar(first, last, &[])
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2 * calendar::EARNINGS_PAGE_SIZE);
+
+        let events = provider.get_upcoming_events(&["AAPL"], u32::MAX).await;
+        assert!(matches!(events, Err(YahooError::InvalidParameter(_))));
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_single_quote() {
         let provider = YahooConnector::new();
-        let response = tokio_test::block_on(provider.get_latest_quotes("HNL.DE", "1d")).unwrap();
+        let response = provider
+            .get_latest_quotes("HNL.DE", Interval::OneDay)
+            .await
+            .unwrap();
         assert_eq!(&response.chart.result[0].meta.symbol, "HNL.DE");
         assert_eq!(&response.chart.result[0].meta.range, "1mo");
         assert_eq!(&response.chart.result[0].meta.data_granularity, "1d");
         let _ = response.last_quote().unwrap();
     }
 
-    #[test]
-    fn test_strange_api_responses() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_strange_api_responses() {
         let provider = YahooConnector::new();
 
         let start = datetime!(2019-07-03 0:00:00.00 UTC);
         let end = datetime!(2020-07-04 23:59:59.99 UTC);
 
-        let resp = tokio_test::block_on(provider.get_quote_history("IBM", start, end)).unwrap();
+        let resp = provider.get_quote_history("IBM", start, end).await.unwrap();
 
         assert_eq!(&resp.chart.result[0].meta.symbol, "IBM");
         assert_eq!(&resp.chart.result[0].meta.data_granularity, "1d");
@@ -239,11 +1174,14 @@ mod tests {
         let _ = resp.last_quote().unwrap();
     }
 
-    #[test]
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
     #[should_panic(expected = "DeserializeFailed")]
-    fn test_api_responses_missing_fields() {
+    async fn test_api_responses_missing_fields() {
         let provider = YahooConnector::new();
-        let response = tokio_test::block_on(provider.get_latest_quotes("BF.B", "1m")).unwrap();
+        let response = provider
+            .get_latest_quotes("BF.B", Interval::OneMinute)
+            .await
+            .unwrap();
 
         assert_eq!(&response.chart.result[0].meta.symbol, "BF.B");
         assert_eq!(&response.chart.result[0].meta.range, "1d");
@@ -251,14 +1189,14 @@ mod tests {
         let _ = response.last_quote().unwrap();
     }
 
-    #[test]
-    fn test_get_quote_history() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_quote_history() {
         let provider = YahooConnector::new();
 
         let start = datetime!(2020-01-01 0:00:00.00 UTC);
         let end = datetime!(2020-01-31 23:59:59.99 UTC);
 
-        let resp = tokio_test::block_on(provider.get_quote_history("AAPL", start, end));
+        let resp = provider.get_quote_history("AAPL", start, end).await;
         if resp.is_ok() {
             let resp = resp.unwrap();
             assert_eq!(resp.chart.result[0].timestamp.len(), 21);
@@ -267,55 +1205,62 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_get_quote_range() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_quote_range() {
         let provider = YahooConnector::new();
-        let response =
-            tokio_test::block_on(provider.get_quote_range("HNL.DE", "1d", "1mo")).unwrap();
+        let response = provider
+            .get_quote_range("HNL.DE", Interval::OneDay, Range::OneMonth)
+            .await
+            .unwrap();
         assert_eq!(&response.chart.result[0].meta.symbol, "HNL.DE");
         assert_eq!(&response.chart.result[0].meta.range, "1mo");
         assert_eq!(&response.chart.result[0].meta.data_granularity, "1d");
         let _ = response.last_quote().unwrap();
     }
 
-    #[test]
-    fn test_get_metadata() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get_metadata() {
         let provider = YahooConnector::new();
-        let response =
-            tokio_test::block_on(provider.get_quote_range("HNL.DE", "1d", "1mo")).unwrap();
+        let response = provider
+            .get_quote_range("HNL.DE", Interval::OneDay, Range::OneMonth)
+            .await
+            .unwrap();
         let metadata = response.metadata().unwrap();
         assert_eq!(metadata.symbol, "HNL.DE");
     }
 
-    #[test]
-    fn test_get() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_get() {
         let provider = YahooConnector::new();
 
         let start = datetime!(2019-01-01 0:00:00.00 UTC);
         let end = datetime!(2020-01-31 23:59:59.99 UTC);
 
-        let response =
-            tokio_test::block_on(provider.get_quote_history_interval("AAPL", start, end, "1mo"))
-                .unwrap();
+        let response = provider
+            .get_quote_history_interval("AAPL", start, end, Interval::OneMonth)
+            .await
+            .unwrap();
         assert_eq!(&response.chart.result[0].timestamp.len(), &13);
         assert_eq!(&response.chart.result[0].meta.data_granularity, "1mo");
         let quotes = response.quotes().unwrap();
         assert_eq!(quotes.len(), 13usize);
     }
 
-    #[test]
-    fn test_large_volume() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_large_volume() {
         let provider = YahooConnector::new();
-        let response =
-            tokio_test::block_on(provider.get_quote_range("BTC-USD", "1d", "5d")).unwrap();
+        let response = provider
+            .get_quote_range("BTC-USD", Interval::OneDay, Range::FiveDays)
+            .await
+            .unwrap();
         let quotes = response.quotes().unwrap();
         assert!(quotes.len() > 0usize);
     }
 
-    #[test]
-    fn test_search_ticker() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_search_ticker() {
         let provider = YahooConnector::new();
-        let resp = tokio_test::block_on(provider.search_ticker("Apple")).unwrap();
+        let resp = provider.search_ticker("Apple").await.unwrap();
 
         assert_eq!(resp.count, 15);
         let mut apple_found = false;
@@ -328,22 +1273,22 @@ mod tests {
         assert!(apple_found)
     }
 
-    #[test]
-    fn search_options() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn search_options() {
         let provider = YahooConnector::new();
-        let resp = tokio_test::block_on(provider.search_options("AAPL")).unwrap();
+        let resp = provider.search_options("AAPL").await.unwrap();
 
         assert!(resp.options.len() > 3);
     }
 
-    #[test]
-    fn test_mutual_fund_history() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_mutual_fund_history() {
         let provider = YahooConnector::new();
 
         let start = datetime!(2020-01-01 0:00:00.00 UTC);
         let end = datetime!(2020-01-31 23:59:59.99 UTC);
 
-        let resp = tokio_test::block_on(provider.get_quote_history("VTSAX", start, end));
+        let resp = provider.get_quote_history("VTSAX", start, end).await;
         if resp.is_ok() {
             let resp = resp.unwrap();
             assert_eq!(resp.chart.result[0].timestamp.len(), 21);
@@ -352,10 +1297,13 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_mutual_fund_latest() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_mutual_fund_latest() {
         let provider = YahooConnector::new();
-        let response = tokio_test::block_on(provider.get_latest_quotes("VTSAX", "1d")).unwrap();
+        let response = provider
+            .get_latest_quotes("VTSAX", Interval::OneDay)
+            .await
+            .unwrap();
 
         assert_eq!(&response.chart.result[0].meta.symbol, "VTSAX");
         assert_eq!(&response.chart.result[0].meta.range, "1mo");
@@ -363,10 +1311,13 @@ mod tests {
         let _ = response.last_quote().unwrap();
     }
 
-    #[test]
-    fn test_mutual_fund_latest_with_null_first_trade_date() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_mutual_fund_latest_with_null_first_trade_date() {
         let provider = YahooConnector::new();
-        let response = tokio_test::block_on(provider.get_latest_quotes("SIWA.F", "1d")).unwrap();
+        let response = provider
+            .get_latest_quotes("SIWA.F", Interval::OneDay)
+            .await
+            .unwrap();
 
         assert_eq!(&response.chart.result[0].meta.symbol, "SIWA.F");
         assert_eq!(&response.chart.result[0].meta.range, "1mo");
@@ -374,20 +1325,25 @@ mod tests {
         let _ = response.last_quote().unwrap();
     }
 
-    #[test]
-    fn test_mutual_fund_range() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_mutual_fund_range() {
         let provider = YahooConnector::new();
-        let response =
-            tokio_test::block_on(provider.get_quote_range("VTSAX", "1d", "1mo")).unwrap();
+        let response = provider
+            .get_quote_range("VTSAX", Interval::OneDay, Range::OneMonth)
+            .await
+            .unwrap();
         assert_eq!(&response.chart.result[0].meta.symbol, "VTSAX");
         assert_eq!(&response.chart.result[0].meta.range, "1mo");
         assert_eq!(&response.chart.result[0].meta.data_granularity, "1d");
     }
 
-    #[test]
-    fn test_mutual_fund_capital_gains() {
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_mutual_fund_capital_gains() {
         let provider = YahooConnector::new();
-        let response = tokio_test::block_on(provider.get_quote_range("AMAGX", "1d", "5y")).unwrap();
+        let response = provider
+            .get_quote_range("AMAGX", Interval::OneDay, Range::FiveYears)
+            .await
+            .unwrap();
 
         assert_eq!(&response.chart.result[0].meta.symbol, "AMAGX");
         assert_eq!(&response.chart.result[0].meta.range, "5y");
@@ -0,0 +1,523 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Duration, OffsetDateTime};
+
+use crate::quote_summary::QuoteSummary;
+use crate::YahooError;
+
+macro_rules! QUERY {
+    () => {
+        "{url}?lang=en-US&region=US"
+    };
+}
+
+/// The number of earnings requested from the visualization API at once
+pub(crate) const EARNINGS_PAGE_SIZE: usize = 100;
+/// The maximum number of pages of earnings requested for one query
+pub(crate) const EARNINGS_MAX_PAGES: usize = 500;
+
+/// The upcoming earnings and dividends of a symbol.
+///
+/// The dates are the dates in UTC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorporateEvents {
+    pub symbol: String,
+    /// The date of the next earnings release, or the first and the last possible date
+    /// if the date is estimated
+    pub earnings_dates: Vec<Date>,
+    pub earnings_date_is_estimate: bool,
+    pub earnings_call_dates: Vec<OffsetDateTime>,
+    pub eps_estimate: Estimate,
+    pub revenue_estimate: Estimate,
+    pub ex_dividend_date: Option<Date>,
+    pub dividend_date: Option<Date>,
+}
+
+/// The consensus of the analysts' estimates
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Estimate {
+    pub average: Option<f64>,
+    pub low: Option<f64>,
+    pub high: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Earnings,
+    EarningsCall,
+    ExDividend,
+    DividendPayment,
+}
+
+/// An event in the calendar of a symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub symbol: String,
+    pub kind: EventKind,
+    pub date: Date,
+    /// The last possible date of an event whose date is estimated as a range
+    pub end_date: Option<Date>,
+    pub is_estimate: bool,
+}
+
+/// The events of multiple symbols, see [`crate::YahooConnector::get_upcoming_events`]
+#[derive(Debug, Default)]
+pub struct UpcomingEvents {
+    /// The events ordered by date
+    pub events: Vec<CalendarEvent>,
+    /// The symbols whose events could not be retrieved
+    pub errors: HashMap<String, YahooError>,
+}
+
+/// An earnings release in the earnings calendar of yahoo! finance
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarningsCalendarEntry {
+    pub symbol: String,
+    pub company_name: Option<String>,
+    pub event_name: Option<String>,
+    pub start: OffsetDateTime,
+    /// When the earnings are released: `BMO` before the market opens, `AMC` after the market
+    /// closes, `TAS` or `TNS` if the time is not supplied
+    pub time_type: Option<String>,
+    pub eps_estimate: Option<f64>,
+    pub eps_actual: Option<f64>,
+    pub eps_surprise_pct: Option<f64>,
+}
+
+impl CorporateEvents {
+    /// Collect the events from the `calendarEvents` module of the quote summary.
+    pub(crate) fn from_summary(symbol: &str, summary: QuoteSummary) -> Result<Self, YahooError> {
+        let calendar = summary.calendar_events.ok_or(YahooError::EmptyDataSet)?;
+
+        let mut events = CorporateEvents {
+            symbol: symbol.to_string(),
+            earnings_dates: vec![],
+            earnings_date_is_estimate: false,
+            earnings_call_dates: vec![],
+            eps_estimate: Estimate::default(),
+            revenue_estimate: Estimate::default(),
+            ex_dividend_date: calendar.ex_dividend_date.map(to_date).transpose()?,
+            dividend_date: calendar.dividend_date.map(to_date).transpose()?,
+        };
+
+        if let Some(earnings) = calendar.earnings {
+            events.earnings_dates = earnings
+                .earnings_date
+                .unwrap_or_default()
+                .into_iter()
+                .map(to_date)
+                .collect::<Result<_, _>>()?;
+            events.earnings_dates.sort();
+            events.earnings_dates.dedup();
+            events.earnings_date_is_estimate = earnings.is_earnings_date_estimate.unwrap_or(false);
+            events.earnings_call_dates = earnings
+                .earnings_call_date
+                .unwrap_or_default()
+                .into_iter()
+                .map(to_datetime)
+                .collect::<Result<_, _>>()?;
+            events.eps_estimate = Estimate {
+                average: earnings.earnings_average,
+                low: earnings.earnings_low,
+                high: earnings.earnings_high,
+            };
+            events.revenue_estimate = Estimate {
+                average: earnings.revenue_average,
+                low: earnings.revenue_low,
+                high: earnings.revenue_high,
+            };
+        }
+
+        Ok(events)
+    }
+
+    /// Return the events as calendar entries, ordered by date.
+    pub fn events(&self) -> Vec<CalendarEvent> {
+        let event = |kind, date, end_date, is_estimate| CalendarEvent {
+            symbol: self.symbol.clone(),
+            kind,
+            date,
+            end_date,
+            is_estimate,
+        };
+
+        let mut events = vec![];
+        if let (Some(first), Some(last)) = (self.earnings_dates.first(), self.earnings_dates.last())
+        {
+            let end_date = if first < last { Some(*last) } else { None };
+            events.push(event(
+                EventKind::Earnings,
+                *first,
+                end_date,
+                self.earnings_date_is_estimate,
+            ));
+        }
+        for call in &self.earnings_call_dates {
+            events.push(event(EventKind::EarningsCall, call.date(), None, false));
+        }
+        if let Some(date) = self.ex_dividend_date {
+            events.push(event(EventKind::ExDividend, date, None, false));
+        }
+        if let Some(date) = self.dividend_date {
+            events.push(event(EventKind::DividendPayment, date, None, false));
+        }
+        events.sort_by_key(|e| e.date);
+        events
+    }
+
+    /// Return the events taking place between the first and the last date, both included.
+    ///
+    /// An event estimated as a range of dates is returned if the range overlaps the period.
+    pub fn events_between(&self, first: Date, last: Date) -> Vec<CalendarEvent> {
+        self.events()
+            .into_iter()
+            .filter(|e| e.date <= last && e.end_date.unwrap_or(e.date) >= first)
+            .collect()
+    }
+}
+
+impl UpcomingEvents {
+    pub(crate) fn collect(
+        results: HashMap<String, Result<CorporateEvents, YahooError>>,
+        first: Date,
+        last: Date,
+    ) -> Self {
+        let mut upcoming = UpcomingEvents::default();
+        for (symbol, result) in results {
+            match result {
+                Ok(events) => upcoming.events.extend(events.events_between(first, last)),
+                Err(e) => {
+                    upcoming.errors.insert(symbol, e);
+                }
+            }
+        }
+        upcoming
+            .events
+            .sort_by(|a, b| (a.date, &a.symbol).cmp(&(b.date, &b.symbol)));
+        upcoming
+    }
+}
+
+pub(crate) fn compose_url(base_url: &str) -> String {
+    format!(QUERY!(), url = base_url)
+}
+
+/// The query of the earnings released from the first to the last date, both included,
+/// restricted to the given symbols unless there are none.
+pub(crate) fn earnings_query(
+    first: Date,
+    last: Date,
+    symbols: &[&str],
+    offset: usize,
+) -> Result<Value, YahooError> {
+    let end = last.checked_add(Duration::days(1)).ok_or_else(|| {
+        YahooError::InvalidParameter(format!("the last date {} is too late", last))
+    })?;
+    let mut operands = vec![
+        json!({"operator": "gte", "operands": ["startdatetime", first.to_string()]}),
+        json!({"operator": "lt", "operands": ["startdatetime", end.to_string()]}),
+    ];
+    if !symbols.is_empty() {
+        let tickers: Vec<Value> = symbols
+            .iter()
+            .map(|s| json!({"operator": "eq", "operands": ["ticker", s]}))
+            .collect();
+        operands.push(json!({"operator": "or", "operands": tickers}));
+    }
+    Ok(json!({
+        "entityIdType": "earnings",
+        "sortField": "startdatetime",
+        "sortType": "ASC",
+        "includeFields": [
+            "ticker",
+            "companyshortname",
+            "eventname",
+            "startdatetime",
+            "startdatetimetype",
+            "epsestimate",
+            "epsactual",
+            "epssurprisepct"
+        ],
+        "query": {"operator": "and", "operands": operands},
+        "offset": offset,
+        "size": EARNINGS_PAGE_SIZE,
+    }))
+}
+
+/// A page of the earnings calendar
+pub(crate) struct EarningsPage {
+    pub entries: Vec<EarningsCalendarEntry>,
+    /// The number of earnings matching the query on all the pages
+    pub total: Option<usize>,
+}
+
+/// Parse the rows of the earnings calendar, which are arrays of the values of the columns.
+pub(crate) fn earnings_from_response(json: Value) -> Result<EarningsPage, YahooError> {
+    let total = json
+        .pointer("/finance/result/0/total")
+        .and_then(Value::as_u64)
+        .map(|total| total as usize);
+    let document = json
+        .pointer("/finance/result/0/documents/0")
+        .ok_or_else(|| {
+            YahooError::FetchFailed(
+                "finance.result.documents not found in the response JSON".into(),
+            )
+        })?;
+    let columns: Vec<&str> = document["columns"]
+        .as_array()
+        .ok_or(YahooError::InvalidJson)?
+        .iter()
+        .map(|c| c["id"].as_str().unwrap_or_default())
+        .collect();
+    let column = |row: &Vec<Value>, id: &str| {
+        columns
+            .iter()
+            .position(|c| *c == id)
+            .and_then(|i| row.get(i))
+            .filter(|v| !v.is_null())
+            .cloned()
+    };
+    let text =
+        |row: &Vec<Value>, id: &str| column(row, id).and_then(|v| v.as_str().map(String::from));
+    let number = |row: &Vec<Value>, id: &str| column(row, id).and_then(|v| v.as_f64());
+
+    let rows = match document["rows"].as_array() {
+        Some(rows) => rows,
+        None => {
+            return Ok(EarningsPage {
+                entries: vec![],
+                total,
+            })
+        }
+    };
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let row = row.as_array().ok_or(YahooError::InvalidJson)?;
+        let (Some(symbol), Some(start)) = (text(row, "ticker"), text(row, "startdatetime")) else {
+            return Err(YahooError::DataInconsistency);
+        };
+        let start = OffsetDateTime::parse(&start, &Rfc3339).map_err(|e| {
+            YahooError::FetchFailed(format!("invalid earnings date {}: {}", start, e))
+        })?;
+        entries.push(EarningsCalendarEntry {
+            symbol,
+            company_name: text(row, "companyshortname"),
+            event_name: text(row, "eventname"),
+            start,
+            time_type: text(row, "startdatetimetype"),
+            eps_estimate: number(row, "epsestimate"),
+            eps_actual: number(row, "epsactual"),
+            eps_surprise_pct: number(row, "epssurprisepct"),
+        });
+    }
+    Ok(EarningsPage { entries, total })
+}
+
+fn to_datetime(timestamp: i64) -> Result<OffsetDateTime, YahooError> {
+    OffsetDateTime::from_unix_timestamp(timestamp).map_err(|_| YahooError::DataInconsistency)
+}
+
+fn to_date(timestamp: i64) -> Result<Date, YahooError> {
+    Ok(to_datetime(timestamp)?.date())
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, datetime};
+
+    use super::*;
+    use crate::quote_summary::from_response;
+
+    fn calendar(symbol: &str, earnings_dates: &str, is_estimate: bool) -> CorporateEvents {
+        let raw = format!(
+            r#"{{"quoteSummary": {{"result": [{{"calendarEvents": {{
+                "maxAge": 1,
+                "earnings": {{
+                    "earningsDate": [{}],
+                    "earningsCallDate": [1730406600],
+                    "isEarningsDateEstimate": {},
+                    "earningsAverage": 1.6,
+                    "earningsLow": 1.55,
+                    "earningsHigh": 1.63,
+                    "revenueAverage": 94504000000,
+                    "revenueLow": 94010000000,
+                    "revenueHigh": 95700000000
+                }},
+                "exDividendDate": 1731024000,
+                "dividendDate": 1731542400
+            }}}}], "error": null}}}}"#,
+            earnings_dates, is_estimate
+        );
+        let summary = from_response(serde_json::from_str(&raw).unwrap()).unwrap();
+        CorporateEvents::from_summary(symbol, summary).unwrap()
+    }
+
+    #[test]
+    fn test_from_summary() {
+        let events = calendar("AAPL", "1730406600", false);
+        assert_eq!(events.earnings_dates, vec![date!(2024 - 10 - 31)]);
+        assert!(!events.earnings_date_is_estimate);
+        assert_eq!(
+            events.earnings_call_dates,
+            vec![datetime!(2024-10-31 20:30:00 UTC)]
+        );
+        assert_eq!(events.eps_estimate.average, Some(1.6));
+        assert_eq!(events.revenue_estimate.high, Some(95700000000.0));
+        assert_eq!(events.ex_dividend_date, Some(date!(2024 - 11 - 08)));
+        assert_eq!(events.dividend_date, Some(date!(2024 - 11 - 14)));
+
+        let summary = from_response(serde_json::json!({
+            "quoteSummary": {"result": [{}], "error": null}
+        }))
+        .unwrap();
+        assert!(matches!(
+            CorporateEvents::from_summary("AAPL", summary),
+            Err(YahooError::EmptyDataSet)
+        ));
+    }
+
+    #[test]
+    fn test_events_between() {
+        // estimated between the 2025-01-28 and the 2025-02-03
+        let events = calendar("MSFT", "1738540800, 1738022400", true);
+        assert_eq!(
+            events.earnings_dates,
+            vec![date!(2025 - 01 - 28), date!(2025 - 02 - 03)]
+        );
+
+        let all = events.events();
+        assert_eq!(
+            all.iter().map(|e| e.kind).collect::<Vec<_>>(),
+            vec![
+                EventKind::EarningsCall,
+                EventKind::ExDividend,
+                EventKind::DividendPayment,
+                EventKind::Earnings
+            ]
+        );
+        assert_eq!(all[3].end_date, Some(date!(2025 - 02 - 03)));
+        assert!(all[3].is_estimate);
+
+        let november = events.events_between(date!(2024 - 11 - 01), date!(2024 - 11 - 30));
+        assert_eq!(november.len(), 2);
+        assert_eq!(november[0].kind, EventKind::ExDividend);
+
+        // the estimated range overlaps the period
+        let february = events.events_between(date!(2025 - 02 - 01), date!(2025 - 02 - 28));
+        assert_eq!(february.len(), 1);
+        assert_eq!(february[0].kind, EventKind::Earnings);
+    }
+
+    #[test]
+    fn test_collect_upcoming() {
+        let mut results = HashMap::new();
+        results.insert(
+            "MSFT".to_string(),
+            Ok(calendar("MSFT", "1730332800", false)),
+        );
+        results.insert(
+            "AAPL".to_string(),
+            Ok(calendar("AAPL", "1730406600", false)),
+        );
+        results.insert("GONE".to_string(), Err(YahooError::EmptyDataSet));
+
+        let upcoming =
+            UpcomingEvents::collect(results, date!(2024 - 10 - 31), date!(2024 - 11 - 10));
+        assert_eq!(
+            upcoming
+                .events
+                .iter()
+                .map(|e| (e.symbol.as_str(), e.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("AAPL", EventKind::Earnings),
+                ("AAPL", EventKind::EarningsCall),
+                ("MSFT", EventKind::Earnings),
+                ("MSFT", EventKind::EarningsCall),
+                ("AAPL", EventKind::ExDividend),
+                ("MSFT", EventKind::ExDividend),
+            ]
+        );
+        assert_eq!(upcoming.errors.len(), 1);
+        assert!(upcoming.errors.contains_key("GONE"));
+    }
+
+    #[test]
+    fn test_earnings_query() {
+        let query = earnings_query(date!(2024 - 11 - 01), date!(2024 - 11 - 07), &[], 0).unwrap();
+        assert_eq!(
+            query["query"]["operands"],
+            serde_json::json!([
+                {"operator": "gte", "operands": ["startdatetime", "2024-11-01"]},
+                {"operator": "lt", "operands": ["startdatetime", "2024-11-08"]}
+            ])
+        );
+        assert_eq!(query["size"], EARNINGS_PAGE_SIZE);
+
+        let query = earnings_query(
+            date!(2024 - 11 - 01),
+            date!(2024 - 11 - 07),
+            &["AAPL", "MSFT"],
+            100,
+        )
+        .unwrap();
+        assert_eq!(
+            query["query"]["operands"][2],
+            serde_json::json!({"operator": "or", "operands": [
+                {"operator": "eq", "operands": ["ticker", "AAPL"]},
+                {"operator": "eq", "operands": ["ticker", "MSFT"]}
+            ]})
+        );
+        assert_eq!(query["offset"], 100);
+
+        assert!(matches!(
+            earnings_query(date!(2024 - 11 - 01), Date::MAX, &[], 0),
+            Err(YahooError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_earnings_from_response() {
+        let response = serde_json::json!({
+            "finance": {
+                "result": [{
+                    "total": 2,
+                    "documents": [{
+                        "entityIdType": "earnings",
+                        "columns": [
+                            {"id": "ticker", "label": "Symbol", "type": "STRING"},
+                            {"id": "companyshortname", "label": "Company", "type": "STRING"},
+                            {"id": "eventname", "label": "Event Name", "type": "STRING"},
+                            {"id": "startdatetime", "label": "Earnings Call Time", "type": "DATETIME"},
+                            {"id": "startdatetimetype", "label": "Time Type", "type": "STRING"},
+                            {"id": "epsestimate", "label": "EPS Estimate", "type": "NUMBER"},
+                            {"id": "epsactual", "label": "Reported EPS", "type": "NUMBER"},
+                            {"id": "epssurprisepct", "label": "Surprise (%)", "type": "NUMBER"}
+                        ],
+                        "rows": [
+                            ["AAPL", "Apple Inc.", "Q4 2024 Earnings Call", "2024-10-31T20:30:00.000Z", "AMC", 1.6, 1.64, 2.56],
+                            ["XOM", "Exxon Mobil Corp", null, "2024-11-01T10:30:00.000Z", "BMO", 1.88, null, null]
+                        ]
+                    }]
+                }],
+                "error": null
+            }
+        });
+        let page = earnings_from_response(response).unwrap();
+        assert_eq!(page.total, Some(2));
+        let entries = page.entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].symbol, "AAPL");
+        assert_eq!(entries[0].start, datetime!(2024-10-31 20:30:00 UTC));
+        assert_eq!(entries[0].time_type.as_deref(), Some("AMC"));
+        assert_eq!(entries[0].eps_actual, Some(1.64));
+        assert_eq!(entries[1].event_name, None);
+        assert_eq!(entries[1].eps_estimate, Some(1.88));
+        assert_eq!(entries[1].eps_surprise_pct, None);
+
+        assert!(earnings_from_response(serde_json::json!({"finance": {"error": "bad"}})).is_err());
+    }
+}
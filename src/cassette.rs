@@ -0,0 +1,290 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::YahooError;
+
+/// Records the responses of yahoo! finance to a file or replays them from it.
+///
+/// In the record mode, every request the connector sends is stored in the cassette file
+/// together with the response. In the replay mode, the responses are served from the file
+/// and nothing is sent over the network. This makes it possible to write deterministic
+/// tests against real yahoo! finance payloads.
+///
+/// The URLs are recorded before the crumb is added to them. The requests sending a payload
+/// are matched by the URL and the payload.
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+}
+
+/// The URL and the payload of a request
+type Request = (String, Option<String>);
+
+enum Mode {
+    Record(Mutex<Vec<Interaction>>),
+    Replay(Mutex<HashMap<Request, VecDeque<Interaction>>>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    payload: Option<String>,
+    status: u16,
+    body: String,
+}
+
+impl Cassette {
+    /// Create a cassette recording all the requests to the file at the given path.
+    ///
+    /// The file is overwritten with each recorded request.
+    pub fn record<P: Into<PathBuf>>(path: P) -> Self {
+        Cassette {
+            path: path.into(),
+            mode: Mode::Record(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Create a cassette replaying the requests recorded in the file at the given path.
+    pub fn replay<P: Into<PathBuf>>(path: P) -> Result<Self, YahooError> {
+        let path = path.into();
+        let data = fs::read_to_string(&path).map_err(|e| {
+            YahooError::CassetteFailed(format!("failed to read {}: {}", path.display(), e))
+        })?;
+        let recorded: Vec<Interaction> = serde_json::from_str(&data)?;
+
+        let mut interactions: HashMap<Request, VecDeque<Interaction>> = HashMap::new();
+        for interaction in recorded {
+            interactions
+                .entry((interaction.url.clone(), interaction.payload.clone()))
+                .or_default()
+                .push_back(interaction);
+        }
+
+        Ok(Cassette {
+            path,
+            mode: Mode::Replay(Mutex::new(interactions)),
+        })
+    }
+
+    pub(crate) fn is_replaying(&self) -> bool {
+        matches!(self.mode, Mode::Replay(_))
+    }
+
+    /// Return the status and the body of the response recorded for the URL and the payload.
+    ///
+    /// Responses recorded for the same request are served in the recorded order, the last one
+    /// is then served repeatedly.
+    pub(crate) fn play(
+        &self,
+        url: &str,
+        payload: Option<&str>,
+    ) -> Result<(u16, String), YahooError> {
+        let Mode::Replay(interactions) = &self.mode else {
+            return Err(YahooError::CassetteFailed(
+                "the cassette is not in the replay mode".into(),
+            ));
+        };
+
+        let mut interactions = interactions.lock().unwrap();
+        let key = (url.to_string(), payload.map(str::to_string));
+        let recorded = interactions.get_mut(&key).ok_or_else(|| {
+            YahooError::CassetteFailed(format!(
+                "no response recorded in {} for {}",
+                self.path.display(),
+                url
+            ))
+        })?;
+
+        let interaction = if recorded.len() > 1 {
+            recorded.pop_front().unwrap()
+        } else {
+            recorded[0].clone()
+        };
+
+        Ok((interaction.status, interaction.body))
+    }
+
+    /// Store the response for the URL and the payload and write the whole cassette to the file.
+    pub(crate) fn save(
+        &self,
+        url: &str,
+        payload: Option<&str>,
+        status: u16,
+        body: &str,
+    ) -> Result<(), YahooError> {
+        let Mode::Record(interactions) = &self.mode else {
+            return Err(YahooError::CassetteFailed(
+                "the cassette is not in the record mode".into(),
+            ));
+        };
+
+        let mut interactions = interactions.lock().unwrap();
+        interactions.push(Interaction {
+            url: url.to_string(),
+            payload: payload.map(str::to_string),
+            status,
+            body: body.to_string(),
+        });
+
+        let data = serde_json::to_string_pretty(&*interactions)?;
+        fs::write(&self.path, data).map_err(|e| {
+            YahooError::CassetteFailed(format!("failed to write {}: {}", self.path.display(), e))
+        })
+    }
+}
+
+/// Record the responses to a temporary cassette file and return a cassette replaying them.
+///
+/// The file is removed, also if the recording fails.
+#[cfg(test)]
+pub(crate) fn with_cassette(name: &str, record: impl FnOnce(&Cassette)) -> Cassette {
+    struct TempFile(PathBuf);
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    let file =
+        TempFile(std::env::temp_dir().join(format!("yahoo_{}_{}.json", name, std::process::id())));
+    record(&Cassette::record(&file.0));
+    Cassette::replay(&file.0).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Interval, Range, YahooConnector};
+
+    const CHART: &str = r#"
+    {
+        "chart": {
+            "result": [
+                {
+                    "meta": {
+                        "currency": "USD",
+                        "symbol": "AAPL",
+                        "exchangeName": "NMS",
+                        "instrumentType": "EQUITY",
+                        "firstTradeDate": 345479400,
+                        "regularMarketTime": 1732309201,
+                        "gmtoffset": -18000,
+                        "timezone": "EST",
+                        "exchangeTimezoneName": "America/New_York",
+                        "regularMarketPrice": 229.87,
+                        "chartPreviousClose": 228.28,
+                        "priceHint": 2,
+                        "currentTradingPeriod": {
+                            "pre": {"timezone": "EST", "start": 1732266000, "end": 1732285800, "gmtoffset": -18000},
+                            "regular": {"timezone": "EST", "start": 1732285800, "end": 1732309200, "gmtoffset": -18000},
+                            "post": {"timezone": "EST", "start": 1732309200, "end": 1732323600, "gmtoffset": -18000}
+                        },
+                        "dataGranularity": "1d",
+                        "range": "5d",
+                        "validRanges": ["1d", "5d", "1mo"]
+                    },
+                    "timestamp": [1732026600, 1732113000],
+                    "indicators": {
+                        "quote": [
+                            {
+                                "volume": [35169600, 36211800],
+                                "high": [229.74, 230.16],
+                                "close": [228.28, 228.52],
+                                "low": [225.17, 226.66],
+                                "open": [226.98, 228.06]
+                            }
+                        ],
+                        "adjclose": [
+                            {"adjclose": [228.28, 228.52]}
+                        ]
+                    }
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    #[test]
+    fn test_record_and_replay() {
+        let player = with_cassette("cassette_record", |recorder| {
+            recorder
+                .save("https://example.com/a", None, 200, "first")
+                .unwrap();
+            recorder
+                .save("https://example.com/a", None, 200, "second")
+                .unwrap();
+            recorder
+                .save("https://example.com/b", None, 404, "missing")
+                .unwrap();
+            recorder
+                .save("https://example.com/a", Some("{\"q\": 1}"), 200, "posted")
+                .unwrap();
+        });
+
+        assert_eq!(
+            player.play("https://example.com/a", None).unwrap(),
+            (200, "first".to_string())
+        );
+        assert_eq!(
+            player.play("https://example.com/a", None).unwrap(),
+            (200, "second".to_string())
+        );
+        assert_eq!(
+            player.play("https://example.com/a", None).unwrap(),
+            (200, "second".to_string())
+        );
+        assert_eq!(
+            player.play("https://example.com/b", None).unwrap(),
+            (404, "missing".to_string())
+        );
+        assert_eq!(
+            player
+                .play("https://example.com/a", Some("{\"q\": 1}"))
+                .unwrap(),
+            (200, "posted".to_string())
+        );
+        assert!(player
+            .play("https://example.com/a", Some("{\"q\": 2}"))
+            .is_err());
+        assert!(player.play("https://example.com/c", None).is_err());
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_connector_replay() {
+        let cassette = with_cassette("cassette_connector", |recorder| {
+            recorder
+                .save(
+                    "https://query1.finance.yahoo.com/v8/finance/chart/AAPL?symbol=AAPL&interval=1d&range=5d&events=div|split|capitalGains",
+                    None,
+                    200,
+                    CHART,
+                )
+                .unwrap();
+        });
+
+        let provider = YahooConnector::builder()
+            .cassette(cassette)
+            .build()
+            .unwrap();
+
+        let response = provider
+            .get_quote_range("AAPL", Interval::OneDay, Range::FiveDays)
+            .await
+            .unwrap();
+        assert_eq!(&response.chart.result[0].meta.symbol, "AAPL");
+        assert_eq!(response.quotes().unwrap().len(), 2);
+        assert_eq!(response.last_quote().unwrap().close, 228.52);
+
+        let missing = provider
+            .get_quote_range("MSFT", Interval::OneDay, Range::FiveDays)
+            .await;
+        assert!(missing.is_err());
+    }
+}
@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 use std::convert::TryFrom as _;
 use std::ops::Deref;
+use std::sync::Arc;
 
 use wreq::{Client, StatusCode, Uri};
 
 use tokio::sync::{self, OnceCell, RwLock};
 use tokio::time::{Duration, Instant};
 
-use crate::YahooError;
+use crate::retry::{self, RetryPolicy};
+use crate::{RateLimiter, YahooError, YahooUrls};
 
 static CSRF_TOKEN_REGEX: OnceCell<regex::Regex> = sync::OnceCell::const_new();
 
@@ -15,6 +17,9 @@ static SESSION_ID_REGEX: OnceCell<regex::Regex> = sync::OnceCell::const_new();
 
 pub(crate) struct Crumb {
     client: Client,
+    urls: YahooUrls,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<Arc<RateLimiter>>,
     data: RwLock<Option<CrumbData>>,
 }
 
@@ -29,9 +34,17 @@ struct CrumbData {
 }
 
 impl Crumb {
-    pub fn new(client: Client) -> Self {
+    pub fn new(
+        client: Client,
+        urls: YahooUrls,
+        retry_policy: Option<RetryPolicy>,
+        rate_limiter: Option<Arc<RateLimiter>>,
+    ) -> Self {
         Self {
             client,
+            urls,
+            retry_policy,
+            rate_limiter,
             data: RwLock::new(None),
         }
     }
@@ -100,42 +113,35 @@ impl Crumb {
         Ok(())
     }
 
+    async fn throttle(&self) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+    }
+
     async fn obtain_crumb(&self) -> Result<String, YahooError> {
-        self.obtain_crumb_attempt().await
-        // for i in 0..10 {
-        //     log::trace!("obtain crumb attempt {}", i);
-        //     match self.obtain_crumb_attempt().await {
-        //         Ok(crumb) => {
-        //             log::trace!("got crumb after {} attempts: {}", i, crumb);
-        //             return Ok(crumb);
-        //         }
-        //         Err(e) => {
-        //             log::trace!("failed to obtain crumb in attempt {}: {}", i, e);
-        //
-        //             // tokio::sleep has a different return value from std::thread::sleep
-        //             #[allow(clippy::let_unit_value)]
-        //             let _ = sleep(Duration::from_secs(1)).await;
-        //         }
-        //     };
-        // }
-        //
-        // Err(YahooError::FetchFailed(
-        //     "failed to obtain the crumb in 10 attempts".into(),
-        // ))
+        retry::retry(self.retry_policy.as_ref(), || async {
+            Ok(self.obtain_crumb_attempt().await?)
+        })
+        .await
     }
 
     async fn obtain_crumb_attempt(&self) -> Result<String, YahooError> {
         // try the easy way first
+        self.throttle().await;
+
         let _ = self
             .client
-            .get("https://fc.yahoo.com")
+            .get(&self.urls.cookie)
             .send()
             .await
             .map_err(|e| YahooError::from_wreq_while(e, "obtaining the session cookie"))?;
 
+        self.throttle().await;
+
         let resp = self
             .client
-            .get("https://query1.finance.yahoo.com/v1/test/getcrumb")
+            .get(&self.urls.crumb)
             .send()
             .await
             .map_err(|e| YahooError::from_wreq_while(e, "obtaining the crumb"))?;
@@ -160,24 +166,30 @@ impl Crumb {
             // the hard way
             let data = self.get_collect_consent_payload(self.get_consent().await?);
 
+            self.throttle().await;
+
             let _ = self
                 .client
-                .post("https://consent.yahoo.com/v2/collectConsent")
+                .post(&self.urls.collect_consent)
                 .form(&data)
                 .send()
                 .await
                 .map_err(|e| YahooError::from_wreq_while(e, "sending the consent"))?;
 
+            self.throttle().await;
+
             self.client
-                .get("https://guce.yahoo.com/copyConsent")
+                .get(&self.urls.copy_consent)
                 .query(&data)
                 .send()
                 .await
                 .map_err(|e| YahooError::from_wreq_while(e, "copying the consent into cookies"))?;
 
+            self.throttle().await;
+
             let resp = self
                 .client
-                .get("https://query1.finance.yahoo.com/v1/test/getcrumb")
+                .get(&self.urls.crumb)
                 .send()
                 .await
                 .map_err(|e| {
@@ -211,9 +223,11 @@ impl Crumb {
     async fn get_consent(&self) -> Result<Consent, YahooError> {
         log::trace!("getting consent");
 
+        self.throttle().await;
+
         let resp = self
             .client
-            .get("https://guce.yahoo.com/consent")
+            .get(&self.urls.consent)
             .send()
             .await
             .map_err(|e| YahooError::from_wreq_while(e, "starting consent negotiation"))?;
@@ -248,6 +262,8 @@ impl Crumb {
 
             log::trace!("we're being redirected to {} to get the consent", full_url);
 
+            self.throttle().await;
+
             let resp = self.client.get(full_url).send().await.map_err(|e| {
                 YahooError::UnexpectedResponse(
                     format!("failed to get the consent web page: {}", e),
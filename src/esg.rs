@@ -0,0 +1,173 @@
+use serde::Deserialize;
+use serde_json::Value;
+use time::OffsetDateTime;
+
+use crate::YahooError;
+
+macro_rules! QUERY {
+    () => {
+        "{url}?symbol={symbol}"
+    };
+}
+
+/// The history of the ESG scores of a symbol and of its peer group
+#[derive(Debug, Clone, PartialEq)]
+pub struct EsgHistory {
+    pub symbol: String,
+    pub peer_group: Option<String>,
+    /// The scores of the symbol, oldest first
+    pub scores: Vec<EsgScorePoint>,
+    /// The average scores of the peer group, oldest first
+    pub peer_scores: Vec<EsgScorePoint>,
+}
+
+/// The ESG scores at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EsgScorePoint {
+    pub date: OffsetDateTime,
+    pub total: Option<f64>,
+    pub environment: Option<f64>,
+    pub social: Option<f64>,
+    pub governance: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EsgChart {
+    symbol: String,
+    peer_group: Option<String>,
+    symbol_series: Option<EsgSeries>,
+    peer_series: Option<EsgSeries>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EsgSeries {
+    #[serde(default)]
+    timestamp: Vec<i64>,
+    #[serde(default)]
+    esg_score: Vec<Option<f64>>,
+    #[serde(default)]
+    environment_score: Vec<Option<f64>>,
+    #[serde(default)]
+    social_score: Vec<Option<f64>>,
+    #[serde(default)]
+    governance_score: Vec<Option<f64>>,
+}
+
+impl EsgSeries {
+    fn points(self) -> Result<Vec<EsgScorePoint>, YahooError> {
+        let value = |values: &[Option<f64>], i: usize| values.get(i).copied().flatten();
+        let mut points = Vec::with_capacity(self.timestamp.len());
+        for (i, timestamp) in self.timestamp.iter().enumerate() {
+            points.push(EsgScorePoint {
+                date: OffsetDateTime::from_unix_timestamp(*timestamp)
+                    .map_err(|_| YahooError::DataInconsistency)?,
+                total: value(&self.esg_score, i),
+                environment: value(&self.environment_score, i),
+                social: value(&self.social_score, i),
+                governance: value(&self.governance_score, i),
+            });
+        }
+        points.sort_by_key(|p| p.date);
+        Ok(points)
+    }
+}
+
+pub(crate) fn compose_url(base_url: &str, symbol: &str) -> String {
+    format!(QUERY!(), url = base_url, symbol = symbol)
+}
+
+pub(crate) fn from_response(mut json: Value) -> Result<EsgHistory, YahooError> {
+    let chart = json
+        .get_mut("esgChart")
+        .and_then(|v| v.get_mut("result"))
+        .and_then(|v| v.get_mut(0))
+        .ok_or_else(|| {
+            YahooError::FetchFailed("esgChart.result not found in the response JSON".into())
+        })?;
+    let chart: EsgChart = serde_json::from_value(chart.take())?;
+    if chart.symbol_series.is_none() {
+        return Err(YahooError::EmptyDataSet);
+    }
+
+    let points = |series: Option<EsgSeries>| series.map(EsgSeries::points).unwrap_or(Ok(vec![]));
+    Ok(EsgHistory {
+        symbol: chart.symbol,
+        peer_group: chart.peer_group,
+        scores: points(chart.symbol_series)?,
+        peer_scores: points(chart.peer_series)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    const ESG_CHART: &str = r#"
+    {
+        "esgChart": {
+            "result": [
+                {
+                    "symbol": "AAPL",
+                    "peerGroup": "Technology Hardware",
+                    "symbolSeries": {
+                        "timestamp": [1414800000, 1417392000, 1420070400],
+                        "esgScore": [61.0, 61.5, null],
+                        "governanceScore": [59.0, 59.0, 60.0],
+                        "environmentScore": [70.0, 71.0, 71.0],
+                        "socialScore": [55.0, 55.0, 56.0]
+                    },
+                    "peerSeries": {
+                        "timestamp": [1414800000, 1417392000],
+                        "esgScore": [53.4, 53.6],
+                        "governanceScore": [56.1, 56.3],
+                        "environmentScore": [51.8, 52.0],
+                        "socialScore": [52.9, 53.0]
+                    }
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    #[test]
+    fn test_compose_url() {
+        assert_eq!(
+            compose_url(
+                "https://query2.finance.yahoo.com/v1/finance/esgChart",
+                "AAPL"
+            ),
+            "https://query2.finance.yahoo.com/v1/finance/esgChart?symbol=AAPL"
+        );
+    }
+
+    #[test]
+    fn test_from_response() {
+        let history = from_response(serde_json::from_str(ESG_CHART).unwrap()).unwrap();
+        assert_eq!(history.symbol, "AAPL");
+        assert_eq!(history.peer_group.as_deref(), Some("Technology Hardware"));
+
+        assert_eq!(history.scores.len(), 3);
+        assert_eq!(history.scores[0].date, datetime!(2014-11-01 0:00:00 UTC));
+        assert_eq!(history.scores[0].total, Some(61.0));
+        assert_eq!(history.scores[1].environment, Some(71.0));
+        assert_eq!(history.scores[2].total, None);
+        assert_eq!(history.scores[2].governance, Some(60.0));
+
+        assert_eq!(history.peer_scores.len(), 2);
+        assert_eq!(history.peer_scores[1].social, Some(53.0));
+
+        let empty = serde_json::json!({
+            "esgChart": {"result": [{"symbol": "EURUSD=X"}], "error": null}
+        });
+        assert!(matches!(
+            from_response(empty),
+            Err(YahooError::EmptyDataSet)
+        ));
+        assert!(from_response(serde_json::json!({"finance": {"error": "Not Found"}})).is_err());
+    }
+}
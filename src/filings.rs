@@ -0,0 +1,220 @@
+use time::macros::format_description;
+use time::{Date, OffsetDateTime};
+
+use crate::quote_summary::{QuoteSummary, SecFilingExhibit};
+use crate::YahooError;
+
+/// A filing of a company with the SEC
+#[derive(Debug, Clone)]
+pub struct Filing {
+    pub date: Date,
+    /// The form of the filing, e.g. `10-K`, `10-Q` or `8-K`
+    pub form_type: String,
+    pub title: Option<String>,
+    pub edgar_url: Option<String>,
+    pub exhibits: Vec<SecFilingExhibit>,
+}
+
+/// Selects the SEC filings by form type and date.
+///
+/// The default filter selects all the filings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilingFilter {
+    form_types: Vec<String>,
+    since: Option<Date>,
+    until: Option<Date>,
+}
+
+impl FilingFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the filings of the form type, e.g. `10-K`. Can be called multiple times to
+    /// select several form types. Amendments like `10-K/A` have to be selected separately.
+    pub fn form_type(mut self, form_type: &str) -> Self {
+        self.form_types.push(form_type.to_string());
+        self
+    }
+
+    /// Select the filings filed on or after the date
+    pub fn since(mut self, date: Date) -> Self {
+        self.since = Some(date);
+        self
+    }
+
+    /// Select the filings filed on or before the date
+    pub fn until(mut self, date: Date) -> Self {
+        self.until = Some(date);
+        self
+    }
+
+    pub fn matches(&self, filing: &Filing) -> bool {
+        (self.form_types.is_empty()
+            || self
+                .form_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&filing.form_type)))
+            && self.since.is_none_or(|since| filing.date >= since)
+            && self.until.is_none_or(|until| filing.date <= until)
+    }
+}
+
+impl Filing {
+    /// Collect the filings from the `secFilings` module of the quote summary, latest first.
+    ///
+    /// Filings without a form type or a date are left out.
+    pub(crate) fn from_summary(summary: QuoteSummary) -> Result<Vec<Self>, YahooError> {
+        let filings = summary
+            .sec_filings
+            .ok_or(YahooError::EmptyDataSet)?
+            .filings
+            .unwrap_or_default();
+
+        let mut result = Vec::with_capacity(filings.len());
+        for filing in filings {
+            let Some(form_type) = filing.filing_type else {
+                continue;
+            };
+            let date = match (filing.epoch_date, filing.date) {
+                (Some(epoch), _) => OffsetDateTime::from_unix_timestamp(epoch)
+                    .map_err(|_| YahooError::DataInconsistency)?
+                    .date(),
+                (None, Some(date)) => {
+                    Date::parse(&date, format_description!("[year]-[month]-[day]")).map_err(
+                        |e| YahooError::FetchFailed(format!("invalid filing date {}: {}", date, e)),
+                    )?
+                }
+                (None, None) => continue,
+            };
+            result.push(Filing {
+                date,
+                form_type,
+                title: filing.title,
+                edgar_url: filing.edgar_url,
+                exhibits: filing.exhibits.unwrap_or_default(),
+            });
+        }
+        result.sort_by_key(|f| std::cmp::Reverse(f.date));
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+    use crate::quote_summary::from_response;
+
+    const FILINGS: &str = r#"
+    {
+        "quoteSummary": {
+            "result": [
+                {
+                    "secFilings": {
+                        "filings": [
+                            {
+                                "date": "2024-11-01",
+                                "epochDate": 1730419200,
+                                "type": "10-K",
+                                "title": "Annual Report",
+                                "edgarUrl": "https://finance.yahoo.com/sec-filing/AAPL/0000320193-24-000123_320193",
+                                "exhibits": [
+                                    {"type": "10-K", "url": "https://cdn.yahoofinance.com/prod/sec-filings/0000320193/000032019324000123/aapl-20240928.htm"}
+                                ],
+                                "maxAge": 1
+                            },
+                            {
+                                "date": "2024-10-31",
+                                "type": "8-K",
+                                "title": "Corporate Changes & Voting Matters",
+                                "maxAge": 1
+                            },
+                            {
+                                "date": "2024-08-02",
+                                "epochDate": 1722556800,
+                                "type": "10-Q",
+                                "title": "Quarterly Report",
+                                "maxAge": 1
+                            },
+                            {
+                                "epochDate": 1722556800,
+                                "title": "Unknown form",
+                                "maxAge": 1
+                            },
+                            {
+                                "date": "2024-05-03",
+                                "epochDate": 1714694400,
+                                "type": "10-Q",
+                                "title": "Quarterly Report",
+                                "maxAge": 1
+                            }
+                        ],
+                        "maxAge": 86400
+                    }
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    fn filings() -> Vec<Filing> {
+        let summary = from_response(serde_json::from_str(FILINGS).unwrap()).unwrap();
+        Filing::from_summary(summary).unwrap()
+    }
+
+    #[test]
+    fn test_from_summary() {
+        let filings = filings();
+        assert_eq!(filings.len(), 4);
+        assert_eq!(filings[0].date, date!(2024 - 11 - 01));
+        assert_eq!(filings[0].form_type, "10-K");
+        assert_eq!(filings[0].exhibits.len(), 1);
+        // the date string is used without an epoch date
+        assert_eq!(filings[1].date, date!(2024 - 10 - 31));
+        assert_eq!(filings[1].edgar_url, None);
+        assert_eq!(filings[3].date, date!(2024 - 05 - 03));
+
+        let summary = from_response(serde_json::json!({
+            "quoteSummary": {"result": [{}], "error": null}
+        }))
+        .unwrap();
+        assert!(matches!(
+            Filing::from_summary(summary),
+            Err(YahooError::EmptyDataSet)
+        ));
+    }
+
+    #[test]
+    fn test_filter() {
+        let filings = filings();
+        let select = |filter: FilingFilter| {
+            filings
+                .iter()
+                .filter(|f| filter.matches(f))
+                .map(|f| f.date)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(select(FilingFilter::new()).len(), 4);
+        assert_eq!(
+            select(FilingFilter::new().form_type("10-q")),
+            vec![date!(2024 - 08 - 02), date!(2024 - 05 - 03)]
+        );
+        assert_eq!(
+            select(FilingFilter::new().form_type("10-K").form_type("8-K")),
+            vec![date!(2024 - 11 - 01), date!(2024 - 10 - 31)]
+        );
+        assert_eq!(
+            select(
+                FilingFilter::new()
+                    .since(date!(2024 - 08 - 02))
+                    .until(date!(2024 - 10 - 31))
+            ),
+            vec![date!(2024 - 10 - 31), date!(2024 - 08 - 02)]
+        );
+        assert!(select(FilingFilter::new().form_type("S-1")).is_empty());
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use crate::quote_summary::{QuoteSummary, TrailingReturns};
+use crate::YahooError;
+
+/// The profile, the composition and the performance of a mutual fund or an ETF
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundInfo {
+    pub family: Option<String>,
+    pub category: Option<String>,
+    pub legal_type: Option<String>,
+    /// The annual expense ratio as a fraction, e.g. `0.0003` for 0.03%
+    pub expense_ratio: Option<f64>,
+    pub turnover: Option<f64>,
+    pub total_net_assets: Option<f64>,
+    pub allocation: AssetAllocation,
+    /// The top holdings, by decreasing weight
+    pub holdings: Vec<FundHolding>,
+    /// The weight of each sector, keyed by yahoo! finance's sector name, e.g. `technology`
+    pub sector_weightings: HashMap<String, f64>,
+    /// The weight of each credit rating of the bonds held, e.g. `aaa`
+    pub bond_ratings: HashMap<String, f64>,
+    pub trailing_returns: Option<TrailingReturns>,
+    /// The trailing returns of the fund category, for comparison
+    pub category_trailing_returns: Option<TrailingReturns>,
+}
+
+/// The share of the fund's assets in each asset class
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AssetAllocation {
+    pub stock: Option<f64>,
+    pub bond: Option<f64>,
+    pub cash: Option<f64>,
+    pub preferred: Option<f64>,
+    pub convertible: Option<f64>,
+    pub other: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundHolding {
+    /// The symbol of the holding, `None` for the holdings not listed on yahoo! finance
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    /// The share of the fund's assets, as a fraction
+    pub weight: f64,
+}
+
+impl FundInfo {
+    /// Collect the fund data from the `topHoldings`, `fundProfile` and `fundPerformance`
+    /// modules of the quote summary.
+    pub(crate) fn from_summary(summary: QuoteSummary) -> Result<Self, YahooError> {
+        if summary.top_holdings.is_none()
+            && summary.fund_profile.is_none()
+            && summary.fund_performance.is_none()
+        {
+            return Err(YahooError::EmptyDataSet);
+        }
+
+        let mut info = FundInfo {
+            family: None,
+            category: None,
+            legal_type: None,
+            expense_ratio: None,
+            turnover: None,
+            total_net_assets: None,
+            allocation: AssetAllocation::default(),
+            holdings: vec![],
+            sector_weightings: HashMap::new(),
+            bond_ratings: HashMap::new(),
+            trailing_returns: None,
+            category_trailing_returns: None,
+        };
+
+        if let Some(profile) = summary.fund_profile {
+            info.family = profile.family;
+            info.category = profile.category_name;
+            info.legal_type = profile.legal_type;
+            if let Some(fees) = profile.fees_expenses_investment {
+                info.expense_ratio = fees.annual_report_expense_ratio.or(fees.net_exp_ratio);
+                info.turnover = fees.annual_holdings_turnover;
+                info.total_net_assets = fees.total_net_assets;
+            }
+        }
+
+        if let Some(top) = summary.top_holdings {
+            info.allocation = AssetAllocation {
+                stock: top.stock_position,
+                bond: top.bond_position,
+                cash: top.cash_position,
+                preferred: top.preferred_position,
+                convertible: top.convertible_position,
+                other: top.other_position,
+            };
+            info.holdings = top
+                .holdings
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|h| {
+                    Some(FundHolding {
+                        symbol: h.symbol,
+                        name: h.holding_name,
+                        weight: h.holding_percent?,
+                    })
+                })
+                .collect();
+            info.holdings.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+            info.sector_weightings = top
+                .sector_weightings
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .collect();
+            info.bond_ratings = top
+                .bond_ratings
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .collect();
+        }
+
+        if let Some(performance) = summary.fund_performance {
+            info.trailing_returns = performance.trailing_returns;
+            info.category_trailing_returns = performance.trailing_returns_cat;
+        }
+
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote_summary::from_response;
+
+    const FUND_SUMMARY: &str = r#"
+    {
+        "quoteSummary": {
+            "result": [
+                {
+                    "topHoldings": {
+                        "maxAge": 1,
+                        "stockPosition": 0.6012,
+                        "bondPosition": 0.3871,
+                        "cashPosition": 0.0117,
+                        "otherPosition": 0,
+                        "preferredPosition": 0,
+                        "convertiblePosition": 0,
+                        "holdings": [
+                            {"symbol": "VTSAX", "holdingName": "Vanguard Total Stock Mkt Idx Adm", "holdingPercent": 0.3605},
+                            {"symbol": "VTBIX", "holdingName": "Vanguard Total Bond Market II Idx I", "holdingPercent": 0.2745},
+                            {"symbol": "VTIAX", "holdingName": "Vanguard Total Intl Stock Index Admiral", "holdingPercent": 0.2407},
+                            {"holdingName": "Vanguard Total Intl Bd II Idx Insl", "holdingPercent": 0.1167}
+                        ],
+                        "equityHoldings": {"priceToEarnings": 0.0507, "priceToBook": 0.3524},
+                        "bondHoldings": {"duration": 6.21, "maturity": 8.4},
+                        "bondRatings": [{"bb": 0}, {"aa": 0.0312}, {"aaa": 0.6946}, {"a": 0.1186}],
+                        "sectorWeightings": [
+                            {"realestate": 0.0318},
+                            {"technology": 0.2625},
+                            {"healthcare": 0.1122}
+                        ]
+                    },
+                    "fundProfile": {
+                        "maxAge": 1,
+                        "family": "Vanguard",
+                        "categoryName": "Moderate Allocation",
+                        "legalType": null,
+                        "feesExpensesInvestment": {
+                            "annualReportExpenseRatio": 0.0008,
+                            "annualHoldingsTurnover": 0.06,
+                            "totalNetAssets": 78960000000
+                        },
+                        "feesExpensesInvestmentCat": {
+                            "annualReportExpenseRatio": 0.0095
+                        }
+                    },
+                    "fundPerformance": {
+                        "maxAge": 1,
+                        "performanceOverview": {
+                            "asOfDate": 1730332800,
+                            "ytdReturnPct": 0.1356,
+                            "fiveYrAvgReturnPct": 0.0812,
+                            "morningStarReturnRating": 4
+                        },
+                        "trailingReturns": {
+                            "asOfDate": 1730332800,
+                            "ytd": 0.1356,
+                            "oneMonth": -0.0187,
+                            "threeMonth": 0.0245,
+                            "oneYear": 0.2417,
+                            "threeYear": 0.0389,
+                            "fiveYear": 0.0812,
+                            "tenYear": 0.0741
+                        },
+                        "trailingReturnsCat": {
+                            "asOfDate": 1730332800,
+                            "ytd": 0.1121,
+                            "oneYear": 0.2153
+                        },
+                        "annualTotalReturns": {
+                            "returns": [{"year": "2023", "annualValue": 0.1769}, {"year": "2022"}],
+                            "returnsCat": []
+                        }
+                    }
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    #[test]
+    fn test_from_summary() {
+        let summary = from_response(serde_json::from_str(FUND_SUMMARY).unwrap()).unwrap();
+        let info = FundInfo::from_summary(summary).unwrap();
+
+        assert_eq!(info.family.as_deref(), Some("Vanguard"));
+        assert_eq!(info.category.as_deref(), Some("Moderate Allocation"));
+        assert_eq!(info.legal_type, None);
+        assert_eq!(info.expense_ratio, Some(0.0008));
+        assert_eq!(info.turnover, Some(0.06));
+        assert_eq!(info.allocation.stock, Some(0.6012));
+        assert_eq!(info.allocation.bond, Some(0.3871));
+
+        assert_eq!(info.holdings.len(), 4);
+        assert_eq!(info.holdings[0].symbol.as_deref(), Some("VTSAX"));
+        assert_eq!(info.holdings[2].weight, 0.2407);
+        // the holding without a symbol is kept with its weight
+        assert_eq!(info.holdings[3].symbol, None);
+        assert_eq!(
+            info.holdings[3].name.as_deref(),
+            Some("Vanguard Total Intl Bd II Idx Insl")
+        );
+        assert_eq!(info.holdings[3].weight, 0.1167);
+        let total: f64 = info.holdings.iter().map(|h| h.weight).sum();
+        assert!((total - 0.9924).abs() < 1e-9);
+
+        assert_eq!(info.sector_weightings.len(), 3);
+        assert_eq!(info.sector_weightings["technology"], 0.2625);
+        assert_eq!(info.bond_ratings["aaa"], 0.6946);
+
+        let returns = info.trailing_returns.unwrap();
+        assert_eq!(returns.one_year, Some(0.2417));
+        assert_eq!(returns.ten_year, Some(0.0741));
+        assert_eq!(returns.last_bull_mkt, None);
+        assert_eq!(info.category_trailing_returns.unwrap().ytd, Some(0.1121));
+    }
+
+    #[test]
+    fn test_not_a_fund() {
+        let summary = from_response(serde_json::json!({
+            "quoteSummary": {"result": [{"price": {"symbol": "AAPL"}}], "error": null}
+        }))
+        .unwrap();
+        assert!(matches!(
+            FundInfo::from_summary(summary),
+            Err(YahooError::EmptyDataSet)
+        ));
+    }
+}
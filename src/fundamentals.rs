@@ -16,6 +16,7 @@ pub(crate) trait AsStr {
 }
 
 pub(crate) fn compose_fundamentals_url<T: AsStr>(
+    base_url: &str,
     symbol: &str,
     period: Period,
     until: time::OffsetDateTime,
@@ -39,7 +40,7 @@ pub(crate) fn compose_fundamentals_url<T: AsStr>(
 
     let url = format!(
         QUERY!(),
-        url = YFUNDAMENTALS_URL,
+        url = base_url,
         symbol = symbol,
         start = start.unix_timestamp(),
         end = until.unix_timestamp(),
@@ -1534,6 +1535,7 @@ mod test {
     #[test]
     fn compose_income_statement_url() {
         let url = super::compose_fundamentals_url(
+            YFUNDAMENTALS_URL,
             "IBM",
             Period::Year,
             OffsetDateTime::from_unix_timestamp(1710248726).unwrap(),
@@ -0,0 +1,426 @@
+use std::fmt;
+use std::str::FromStr;
+
+use time::{Date, Duration, OffsetDateTime, Time};
+
+use crate::YahooError;
+
+/// The interval between two consecutive quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    OneMinute,
+    TwoMinutes,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    SixtyMinutes,
+    NinetyMinutes,
+    OneHour,
+    OneDay,
+    FiveDays,
+    OneWeek,
+    OneMonth,
+    ThreeMonths,
+}
+
+impl Interval {
+    pub fn all() -> &'static [Self] {
+        &[
+            Interval::OneMinute,
+            Interval::TwoMinutes,
+            Interval::FiveMinutes,
+            Interval::FifteenMinutes,
+            Interval::ThirtyMinutes,
+            Interval::SixtyMinutes,
+            Interval::NinetyMinutes,
+            Interval::OneHour,
+            Interval::OneDay,
+            Interval::FiveDays,
+            Interval::OneWeek,
+            Interval::OneMonth,
+            Interval::ThreeMonths,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::OneMinute => "1m",
+            Interval::TwoMinutes => "2m",
+            Interval::FiveMinutes => "5m",
+            Interval::FifteenMinutes => "15m",
+            Interval::ThirtyMinutes => "30m",
+            Interval::SixtyMinutes => "60m",
+            Interval::NinetyMinutes => "90m",
+            Interval::OneHour => "1h",
+            Interval::OneDay => "1d",
+            Interval::FiveDays => "5d",
+            Interval::OneWeek => "1wk",
+            Interval::OneMonth => "1mo",
+            Interval::ThreeMonths => "3mo",
+        }
+    }
+
+    /// How far back in the past yahoo! finance provides quotes with this interval
+    pub fn max_lookback(&self) -> Option<Duration> {
+        match self {
+            Interval::OneMinute => Some(Duration::days(30)),
+            Interval::TwoMinutes
+            | Interval::FiveMinutes
+            | Interval::FifteenMinutes
+            | Interval::ThirtyMinutes
+            | Interval::NinetyMinutes => Some(Duration::days(60)),
+            Interval::SixtyMinutes | Interval::OneHour => Some(Duration::days(730)),
+            _ => None,
+        }
+    }
+
+    /// The longest period yahoo! finance returns quotes with this interval for in one request
+    pub fn max_span(&self) -> Option<Duration> {
+        match self {
+            Interval::OneMinute => Some(Duration::days(7)),
+            _ => self.max_lookback(),
+        }
+    }
+
+    /// The longest range up to one month yahoo! finance returns quotes with this interval for
+    pub(crate) fn latest_range(&self) -> Range {
+        [Range::OneMonth, Range::FiveDays]
+            .iter()
+            .copied()
+            .find(|range| self.check_range(*range).is_ok())
+            .unwrap_or(Range::OneDay)
+    }
+
+    /// Check that yahoo! finance provides the quotes with this interval for the given period.
+    ///
+    /// Periods longer than [`Interval::max_span`] are fine, they are split into multiple requests.
+    pub fn check_period(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Result<(), YahooError> {
+        self.check_period_at(start, end, OffsetDateTime::now_utc())
+    }
+
+    fn check_period_at(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        now: OffsetDateTime,
+    ) -> Result<(), YahooError> {
+        if start > end {
+            return Err(YahooError::InvalidParameter(format!(
+                "the start {} is after the end {}",
+                start, end
+            )));
+        }
+        if let Some(lookback) = self.max_lookback() {
+            if start < now - lookback {
+                return Err(YahooError::InvalidParameter(format!(
+                    "quotes with the interval {} are only available for the last {} days",
+                    self,
+                    lookback.whole_days()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Split the period into the windows yahoo! finance returns the quotes for in one request.
+    ///
+    /// Consecutive windows share their boundary, the quote at the boundary is therefore
+    /// returned twice.
+    pub(crate) fn windows(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Vec<(OffsetDateTime, OffsetDateTime)> {
+        let span = match self.max_span() {
+            Some(span) => span,
+            None => return vec![(start, end)],
+        };
+        let mut windows = vec![];
+        let mut window_start = start;
+        loop {
+            let window_end = (window_start + span).min(end);
+            windows.push((window_start, window_end));
+            if window_end >= end {
+                return windows;
+            }
+            window_start = window_end;
+        }
+    }
+
+    /// Check that yahoo! finance provides the quotes with this interval for the given range.
+    pub fn check_range(&self, range: Range) -> Result<(), YahooError> {
+        self.check_range_at(range, OffsetDateTime::now_utc())
+    }
+
+    fn check_range_at(&self, range: Range, now: OffsetDateTime) -> Result<(), YahooError> {
+        let span = match self.max_span() {
+            Some(span) => span,
+            None => return Ok(()),
+        };
+        match range.max_duration_at(now) {
+            Some(duration) if duration <= span => Ok(()),
+            _ => Err(YahooError::InvalidParameter(format!(
+                "quotes with the interval {} can only be requested for {} days at once, not for the range {}",
+                self,
+                span.whole_days(),
+                range
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Interval {
+    type Err = YahooError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Interval::all()
+            .iter()
+            .find(|i| i.as_str() == s)
+            .copied()
+            .ok_or_else(|| YahooError::InvalidParameter(format!("unknown interval '{}'", s)))
+    }
+}
+
+/// The period of time up to now to retrieve the quotes for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Range {
+    OneDay,
+    FiveDays,
+    OneMonth,
+    ThreeMonths,
+    SixMonths,
+    OneYear,
+    TwoYears,
+    FiveYears,
+    TenYears,
+    YearToDate,
+    Max,
+}
+
+impl Range {
+    pub fn all() -> &'static [Self] {
+        &[
+            Range::OneDay,
+            Range::FiveDays,
+            Range::OneMonth,
+            Range::ThreeMonths,
+            Range::SixMonths,
+            Range::OneYear,
+            Range::TwoYears,
+            Range::FiveYears,
+            Range::TenYears,
+            Range::YearToDate,
+            Range::Max,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Range::OneDay => "1d",
+            Range::FiveDays => "5d",
+            Range::OneMonth => "1mo",
+            Range::ThreeMonths => "3mo",
+            Range::SixMonths => "6mo",
+            Range::OneYear => "1y",
+            Range::TwoYears => "2y",
+            Range::FiveYears => "5y",
+            Range::TenYears => "10y",
+            Range::YearToDate => "ytd",
+            Range::Max => "max",
+        }
+    }
+
+    /// The longest the range can be at `now`, `None` for the whole history.
+    fn max_duration_at(&self, now: OffsetDateTime) -> Option<Duration> {
+        match self {
+            Range::OneDay => Some(Duration::days(1)),
+            Range::FiveDays => Some(Duration::days(5)),
+            Range::OneMonth => Some(Duration::days(31)),
+            Range::ThreeMonths => Some(Duration::days(92)),
+            Range::SixMonths => Some(Duration::days(184)),
+            Range::OneYear => Some(Duration::days(365)),
+            Range::TwoYears => Some(Duration::days(730)),
+            Range::FiveYears => Some(Duration::days(1826)),
+            Range::TenYears => Some(Duration::days(3652)),
+            Range::YearToDate => {
+                let new_year = Date::from_ordinal_date(now.year(), 1).ok()?;
+                Some(now - now.replace_date(new_year).replace_time(Time::MIDNIGHT))
+            }
+            Range::Max => None,
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Range {
+    type Err = YahooError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Range::all()
+            .iter()
+            .find(|r| r.as_str() == s)
+            .copied()
+            .ok_or_else(|| YahooError::InvalidParameter(format!("unknown range '{}'", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        for interval in Interval::all() {
+            assert_eq!(interval.to_string().parse::<Interval>().unwrap(), *interval);
+        }
+        for range in Range::all() {
+            assert_eq!(range.to_string().parse::<Range>().unwrap(), *range);
+        }
+        assert_eq!("1wk".parse::<Interval>().unwrap(), Interval::OneWeek);
+        assert_eq!("ytd".parse::<Range>().unwrap(), Range::YearToDate);
+        assert!(matches!(
+            "1day".parse::<Interval>(),
+            Err(YahooError::InvalidParameter(_))
+        ));
+        assert!("1w".parse::<Range>().is_err());
+    }
+
+    #[test]
+    fn test_check_period() {
+        let now = datetime!(2024-06-30 12:00:00 UTC);
+
+        assert!(Interval::OneMinute
+            .check_period_at(
+                datetime!(2024-06-25 0:00:00 UTC),
+                datetime!(2024-06-30 0:00:00 UTC),
+                now
+            )
+            .is_ok());
+        // too old
+        assert!(Interval::OneMinute
+            .check_period_at(
+                datetime!(2024-05-01 0:00:00 UTC),
+                datetime!(2024-05-02 0:00:00 UTC),
+                now
+            )
+            .is_err());
+        // longer than one request, but still available
+        assert!(Interval::OneMinute
+            .check_period_at(
+                datetime!(2024-06-10 0:00:00 UTC),
+                datetime!(2024-06-20 0:00:00 UTC),
+                now
+            )
+            .is_ok());
+        assert!(Interval::OneHour
+            .check_period_at(
+                datetime!(2023-01-01 0:00:00 UTC),
+                datetime!(2024-01-01 0:00:00 UTC),
+                now
+            )
+            .is_ok());
+        assert!(Interval::OneDay
+            .check_period_at(
+                datetime!(1990-01-01 0:00:00 UTC),
+                datetime!(2024-01-01 0:00:00 UTC),
+                now
+            )
+            .is_ok());
+        // reversed
+        assert!(Interval::OneDay
+            .check_period_at(
+                datetime!(2024-01-01 0:00:00 UTC),
+                datetime!(2023-01-01 0:00:00 UTC),
+                now
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_windows() {
+        let start = datetime!(2024-06-01 0:00:00 UTC);
+
+        assert_eq!(
+            Interval::OneMinute.windows(start, datetime!(2024-06-05 0:00:00 UTC)),
+            vec![(start, datetime!(2024-06-05 0:00:00 UTC))]
+        );
+        assert_eq!(
+            Interval::OneMinute.windows(start, datetime!(2024-06-20 0:00:00 UTC)),
+            vec![
+                (start, datetime!(2024-06-08 0:00:00 UTC)),
+                (
+                    datetime!(2024-06-08 0:00:00 UTC),
+                    datetime!(2024-06-15 0:00:00 UTC)
+                ),
+                (
+                    datetime!(2024-06-15 0:00:00 UTC),
+                    datetime!(2024-06-20 0:00:00 UTC)
+                ),
+            ]
+        );
+        assert_eq!(
+            Interval::OneDay.windows(datetime!(1990-01-01 0:00:00 UTC), start),
+            vec![(datetime!(1990-01-01 0:00:00 UTC), start)]
+        );
+    }
+
+    #[test]
+    fn test_check_range() {
+        assert!(Interval::OneMinute.check_range(Range::FiveDays).is_ok());
+        assert!(Interval::OneMinute.check_range(Range::OneMonth).is_err());
+        assert!(Interval::OneMinute.check_range(Range::Max).is_err());
+        assert!(Interval::SixtyMinutes.check_range(Range::Max).is_err());
+        assert!(Interval::FiveMinutes.check_range(Range::OneMonth).is_ok());
+        assert!(Interval::FiveMinutes
+            .check_range(Range::ThreeMonths)
+            .is_err());
+        assert!(Interval::OneHour.check_range(Range::TwoYears).is_ok());
+        assert!(Interval::OneHour.check_range(Range::FiveYears).is_err());
+        assert!(Interval::OneDay.check_range(Range::Max).is_ok());
+
+        let january = datetime!(2024-01-05 12:00 UTC);
+        let july = datetime!(2024-07-05 12:00 UTC);
+        assert!(Interval::OneMinute
+            .check_range_at(Range::YearToDate, january)
+            .is_ok());
+        assert!(Interval::OneMinute
+            .check_range_at(Range::YearToDate, july)
+            .is_err());
+        assert!(Interval::FiveMinutes
+            .check_range_at(Range::YearToDate, july)
+            .is_err());
+        assert!(Interval::OneHour
+            .check_range_at(Range::YearToDate, july)
+            .is_ok());
+        assert!(Interval::OneDay
+            .check_range_at(Range::YearToDate, july)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_latest_range() {
+        assert_eq!(Interval::OneMinute.latest_range(), Range::FiveDays);
+        assert_eq!(Interval::TwoMinutes.latest_range(), Range::OneMonth);
+        assert_eq!(Interval::OneDay.latest_range(), Range::OneMonth);
+        for interval in Interval::all() {
+            assert!(interval.check_range(interval.latest_range()).is_ok());
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crumb::Crumb;
+use std::sync::Arc;
 use std::time::Duration;
 use time::OffsetDateTime;
 use wreq::Emulation;
@@ -8,18 +9,47 @@ use wreq::{Client, ClientBuilder, EmulationFactory};
 // re-export time crate
 pub use time;
 
+mod adjustment;
+mod calendar;
+mod cassette;
 mod crumb;
+mod esg;
+mod filings;
+mod fund;
 pub mod fundamentals;
-mod options;
+mod interval;
+pub mod options;
+mod ownership;
+pub mod pricing;
+mod quote_snapshot;
 pub mod quote_summary;
 mod quotes;
+mod rate_limiter;
+mod recommendations;
+mod retry;
 mod search_result;
+#[cfg(feature = "streaming")]
+pub mod streaming;
 mod yahoo_error;
 
+pub use adjustment::Adjustment;
+pub use calendar::{
+    CalendarEvent, CorporateEvents, EarningsCalendarEntry, Estimate, EventKind, UpcomingEvents,
+};
+pub use cassette::Cassette;
+pub use esg::{EsgHistory, EsgScorePoint};
+pub use filings::{Filing, FilingFilter};
+pub use fund::{AssetAllocation, FundHolding, FundInfo};
+pub use interval::{Interval, Range};
+pub use ownership::{InsiderActivity, InsiderTrade, InsiderTradeKind, OwnershipInfo};
+pub use quote_snapshot::{MarketState, QuoteSnapshot};
 pub use quotes::{
-    AdjClose, CapitalGain, Dividend, PeriodInfo, Quote, QuoteBlock, QuoteList, Split,
-    TradingPeriods, YChart, YMetaData, YQuoteBlock, YResponse,
+    AdjClose, CapitalGain, Dividend, PartialQuote, PeriodInfo, Quote, QuoteBlock, QuoteList,
+    QuoteReport, Session, Split, TradingPeriods, YChart, YMetaData, YQuoteBlock, YResponse,
 };
+pub use rate_limiter::RateLimiter;
+pub use recommendations::{RatingAction, RatingChange, RecommendationCounts, Recommendations};
+pub use retry::RetryPolicy;
 pub use search_result::{
     YNewsItem, YOptionResult, YOptionResults, YQuoteItem, YQuoteItemOpt, YSearchResult,
     YSearchResultOpt,
@@ -30,7 +60,19 @@ const YCHART_URL: &str = "https://query1.finance.yahoo.com/v8/finance/chart";
 const YSEARCH_URL: &str = "https://query2.finance.yahoo.com/v1/finance/search";
 const YFUNDAMENTALS_URL: &str =
     "https://query2.finance.yahoo.com/ws/fundamentals-timeseries/v1/finance/timeseries";
+const YQUOTE_SUMMARY_URL: &str = "https://query2.finance.yahoo.com/v10/finance/quoteSummary";
+const YQUOTE_URL: &str = "https://query1.finance.yahoo.com/v7/finance/quote";
+const YESG_CHART_URL: &str = "https://query2.finance.yahoo.com/v1/finance/esgChart";
+const YVISUALIZATION_URL: &str = "https://query1.finance.yahoo.com/v1/finance/visualization";
 const OPTIONS_URL: &str = "https://query2.finance.yahoo.com/v7/finance/options";
+const STREAMER_URL: &str = "wss://streamer.finance.yahoo.com/?version=2";
+const COOKIE_URL: &str = "https://fc.yahoo.com";
+const CRUMB_URL: &str = "https://query1.finance.yahoo.com/v1/test/getcrumb";
+const CONSENT_URL: &str = "https://guce.yahoo.com/consent";
+const COLLECT_CONSENT_URL: &str = "https://consent.yahoo.com/v2/collectConsent";
+const COPY_CONSENT_URL: &str = "https://guce.yahoo.com/copyConsent";
+
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
 
 const DEFAULT_USER_AGENT_HEADER: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_10_1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/39.0.2171.95 Safari/537.36";
 
@@ -56,12 +98,98 @@ macro_rules! YTICKER_QUERY {
     };
 }
 
+/// The URLs of the yahoo! finance endpoints used by the connector.
+///
+/// By default, all of them point to the yahoo! finance servers. Use [`YahooUrls::with_host`]
+/// to redirect all requests to a single server, e.g. a local stand-in used in tests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YahooUrls {
+    /// The chart API used for the quote histories
+    pub chart: String,
+    /// The ticker search API
+    pub search: String,
+    /// The fundamentals timeseries API
+    pub fundamentals: String,
+    /// The quote summary API
+    pub quote_summary: String,
+    /// The options API
+    pub options: String,
+    /// The quote API used for the current quotes of multiple symbols
+    pub quote: String,
+    /// The ESG chart API used for the history of the ESG scores
+    pub esg_chart: String,
+    /// The visualization API used for the earnings calendar
+    pub visualization: String,
+    /// The websocket streaming the real-time quotes (used with the `streaming` feature)
+    pub streamer: String,
+    /// The page visited to obtain the session cookie
+    pub cookie: String,
+    /// The API returning the crumb for the current session
+    pub crumb: String,
+    /// The page starting the consent negotiation
+    pub consent: String,
+    /// The form the consent is posted to
+    pub collect_consent: String,
+    /// The page copying the consent into the cookies
+    pub copy_consent: String,
+}
+
+impl YahooUrls {
+    /// Create the URLs of all the endpoints on the given host (e.g. "http://localhost:8080"),
+    /// keeping the paths used by yahoo! finance.
+    pub fn with_host(host: &str) -> Self {
+        let host = host.trim_end_matches('/');
+        YahooUrls {
+            chart: format!("{}/v8/finance/chart", host),
+            search: format!("{}/v1/finance/search", host),
+            fundamentals: format!("{}/ws/fundamentals-timeseries/v1/finance/timeseries", host),
+            quote_summary: format!("{}/v10/finance/quoteSummary", host),
+            options: format!("{}/v7/finance/options", host),
+            quote: format!("{}/v7/finance/quote", host),
+            esg_chart: format!("{}/v1/finance/esgChart", host),
+            visualization: format!("{}/v1/finance/visualization", host),
+            streamer: format!("{}/?version=2", host.replacen("http", "ws", 1)),
+            cookie: host.to_string(),
+            crumb: format!("{}/v1/test/getcrumb", host),
+            consent: format!("{}/consent", host),
+            collect_consent: format!("{}/v2/collectConsent", host),
+            copy_consent: format!("{}/copyConsent", host),
+        }
+    }
+}
+
+impl Default for YahooUrls {
+    fn default() -> Self {
+        YahooUrls {
+            chart: YCHART_URL.into(),
+            search: YSEARCH_URL.into(),
+            fundamentals: YFUNDAMENTALS_URL.into(),
+            quote_summary: YQUOTE_SUMMARY_URL.into(),
+            options: OPTIONS_URL.into(),
+            quote: YQUOTE_URL.into(),
+            esg_chart: YESG_CHART_URL.into(),
+            visualization: YVISUALIZATION_URL.into(),
+            streamer: STREAMER_URL.into(),
+            cookie: COOKIE_URL.into(),
+            crumb: CRUMB_URL.into(),
+            consent: CONSENT_URL.into(),
+            collect_consent: COLLECT_CONSENT_URL.into(),
+            copy_consent: COPY_CONSENT_URL.into(),
+        }
+    }
+}
+
 /// Container for connection parameters to yahoo! finance server
 pub struct YahooConnector {
     client: Client,
     crumb: Crumb,
-    url: &'static str,
-    search_url: &'static str,
+    urls: YahooUrls,
+    cassette: Option<Cassette>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    max_concurrency: usize,
+    #[cfg(feature = "blocking")]
+    runtime: tokio::runtime::Runtime,
 }
 
 #[derive(Default)]
@@ -69,6 +197,11 @@ pub struct YahooConnectorBuilder {
     client_builder: Option<ClientBuilder>,
     emulation: Option<Emulation>,
     timeout: Option<Duration>,
+    urls: Option<YahooUrls>,
+    cassette: Option<Cassette>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    max_concurrency: Option<usize>,
 }
 
 impl YahooConnector {
@@ -108,13 +241,34 @@ impl YahooConnectorBuilder {
             .build()
             .map_err(|e| YahooError::from_wreq_while(e, "building the client"))?;
 
-        let crumb = Crumb::new(client.clone());
+        let urls = self.urls.unwrap_or_default();
+
+        let crumb = Crumb::new(
+            client.clone(),
+            urls.clone(),
+            self.retry_policy.clone(),
+            self.rate_limiter.clone(),
+        );
+
+        #[cfg(feature = "blocking")]
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(YahooError::RuntimeFailed)?;
 
         Ok(YahooConnector {
             client,
             crumb,
-            url: YCHART_URL,
-            search_url: YSEARCH_URL,
+            urls,
+            cassette: self.cassette,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            max_concurrency: self
+                .max_concurrency
+                .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+                .max(1),
+            #[cfg(feature = "blocking")]
+            runtime,
         })
     }
 
@@ -132,35 +286,98 @@ impl YahooConnectorBuilder {
         self.emulation = Some(emulation.emulation());
         self
     }
+
+    /// Use the given URLs instead of the yahoo! finance ones.
+    pub fn urls(mut self, urls: YahooUrls) -> Self {
+        self.urls = Some(urls);
+        self
+    }
+
+    /// Record the responses to the cassette or replay them from it.
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Retry the requests failing for a transient reason according to the policy.
+    /// By default, the requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Pass all the requests, including the ones obtaining the crumb, through the rate limiter.
+    /// The same limiter can be shared by multiple connectors.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// The maximum number of requests sent concurrently by the methods fetching data for
+    /// multiple tickers at once. Defaults to 4.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
 }
 
 pub mod async_impl;
 
 #[cfg(test)]
 mod tests {
-    use crate::{quote_summary::QuoteSummaryField, YahooConnector};
+    use crate::{quote_summary::QuoteSummaryField, YahooConnector, YahooUrls};
 
     #[test]
-    fn test_quote_summary_live() {
-        tokio_test::block_on(async {
-            env_logger::builder()
-                .filter_level(log::LevelFilter::Trace)
-                .init();
-
-            let c = YahooConnector::new();
-            let res = c
-                .get_quote_summary(
-                    "AAPL",
-                    &[
-                        QuoteSummaryField::EarningsTrend,
-                        QuoteSummaryField::Earnings,
-                        QuoteSummaryField::DefaultKeyStatistics,
-                    ],
-                )
-                .await;
-            assert!(res.is_ok());
-            println!("{:?}", res.unwrap());
-            panic!("just to see the output");
-        });
+    fn test_urls_with_host() {
+        let urls = YahooUrls::with_host("http://localhost:8080/");
+        assert_eq!(urls.chart, "http://localhost:8080/v8/finance/chart");
+        assert_eq!(urls.cookie, "http://localhost:8080");
+        assert_eq!(urls.crumb, "http://localhost:8080/v1/test/getcrumb");
+        assert_eq!(urls.quote, "http://localhost:8080/v7/finance/quote");
+        assert_eq!(
+            urls.visualization,
+            "http://localhost:8080/v1/finance/visualization"
+        );
+        assert_eq!(urls.streamer, "ws://localhost:8080/?version=2");
+        assert_eq!(
+            YahooUrls::with_host("https://example.com").streamer,
+            "wss://example.com/?version=2"
+        );
+
+        let default = YahooUrls::default();
+        assert_eq!(
+            YahooUrls::with_host("https://query2.finance.yahoo.com").quote_summary,
+            default.quote_summary
+        );
+        assert_eq!(
+            YahooUrls::with_host("https://query2.finance.yahoo.com").esg_chart,
+            default.esg_chart
+        );
+        assert_eq!(
+            YahooUrls::with_host("https://query1.finance.yahoo.com").visualization,
+            default.visualization
+        );
+    }
+
+    #[maybe_async::test(feature = "blocking", async(not(feature = "blocking"), tokio::test))]
+    async fn test_quote_summary_live() {
+        env_logger::builder()
+            .filter_level(log::LevelFilter::Trace)
+            .init();
+
+        let c = YahooConnector::new();
+        let res = c
+            .get_quote_summary(
+                "AAPL",
+                &[
+                    QuoteSummaryField::EarningsTrend,
+                    QuoteSummaryField::Earnings,
+                    QuoteSummaryField::DefaultKeyStatistics,
+                ],
+            )
+            .await;
+        assert!(res.is_ok());
+        println!("{:?}", res.unwrap());
+        panic!("just to see the output");
     }
 }
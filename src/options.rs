@@ -1,18 +1,35 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
 use serde::Deserialize;
 use serde_json::Value;
-use time::OffsetDateTime;
+use time::{Date, Month, OffsetDateTime};
 
-use crate::{YahooError, OPTIONS_URL};
+use crate::{QuoteSnapshot, YahooError};
 
-pub fn compose_options_url(symbol: &str) -> String {
-    format!("{}/{}", OPTIONS_URL, symbol)
+pub(crate) fn compose_options_url(base_url: &str, symbol: &str) -> String {
+    format!("{}/{}", base_url, symbol)
 }
 
-pub fn compose_option_chain_url(symbol: &str, date: OffsetDateTime) -> String {
-    format!("{}/{}?date={}", OPTIONS_URL, symbol, date.unix_timestamp())
+pub(crate) fn compose_option_chain_url(
+    base_url: &str,
+    symbol: &str,
+    date: OffsetDateTime,
+    query: &OptionChainQuery,
+) -> String {
+    let mut url = format!("{}/{}?date={}", base_url, symbol, date.unix_timestamp());
+    if let Some((min, max)) = query.strike_range() {
+        url = format!("{}&strikeMin={}&strikeMax={}", url, min, max);
+    }
+    if query.straddle {
+        url.push_str("&straddle=true");
+    }
+    url
 }
 
-pub fn options_from_response(mut response: Value) -> Result<Options, YahooError> {
+pub(crate) fn options_from_response(mut response: Value) -> Result<Options, YahooError> {
     if let Some(Value::Array(mut results)) = response
         .get_mut("optionChain")
         .and_then(|v| v.get_mut("result"))
@@ -33,7 +50,7 @@ pub fn options_from_response(mut response: Value) -> Result<Options, YahooError>
     }
 }
 
-pub fn option_chain_from_response(mut response: Value) -> Result<OptionChain, YahooError> {
+pub(crate) fn option_chain_from_response(mut response: Value) -> Result<OptionChain, YahooError> {
     if let Some(option_chain) = response
         .get_mut("optionChain")
         .and_then(|v| v.get_mut("result"))
@@ -44,7 +61,17 @@ pub fn option_chain_from_response(mut response: Value) -> Result<OptionChain, Ya
         .and_then(|v| v.get_mut(0))
         .map(|v| v.take())
     {
-        serde_json::from_value(option_chain).map_err(YahooError::DeserializeFailed)
+        let option_chain = split_straddles(option_chain);
+        let mut option_chain: OptionChain =
+            serde_json::from_value(option_chain).map_err(YahooError::DeserializeFailed)?;
+        for contract in option_chain
+            .calls
+            .iter_mut()
+            .chain(option_chain.puts.iter_mut())
+        {
+            contract.occ = contract.contract_symbol.parse().ok();
+        }
+        Ok(option_chain)
     } else {
         Err(YahooError::FetchFailed(
             "could not find 'optionChain.result[0].options[0]' in the response".into(),
@@ -52,11 +79,33 @@ pub fn option_chain_from_response(mut response: Value) -> Result<OptionChain, Ya
     }
 }
 
+/// The options of the `straddle` format are listed as pairs of a call and a put of the same strike
+fn split_straddles(mut option_chain: Value) -> Value {
+    if let Some(Value::Array(straddles)) = option_chain.get_mut("straddles").map(|v| v.take()) {
+        let mut calls = vec![];
+        let mut puts = vec![];
+        for mut straddle in straddles {
+            for (side, contracts) in [("call", &mut calls), ("put", &mut puts)] {
+                match straddle.get_mut(side).map(|v| v.take()) {
+                    Some(Value::Null) | None => {}
+                    Some(contract) => contracts.push(contract),
+                }
+            }
+        }
+        option_chain["calls"] = Value::Array(calls);
+        option_chain["puts"] = Value::Array(puts);
+    }
+    option_chain
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
     pub expiration_dates: Vec<i64>,
     pub strikes: Vec<f64>,
+    /// The quote of the underlying
+    #[serde(default)]
+    pub quote: Option<QuoteSnapshot>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -70,21 +119,349 @@ pub struct OptionChain {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OptionContract {
+    /// The OCC symbol of the contract, e.g. `AAPL241206C00100000`
+    pub contract_symbol: String,
+    /// The parts of the contract symbol, `None` if it is not a valid OCC symbol
+    #[serde(skip)]
+    pub occ: Option<OccSymbol>,
     pub strike: f64,
     pub currency: String,
     pub last_price: f64,
     pub change: f64,
     pub percent_change: f64,
+    /// The number of contracts traded during the day
+    #[serde(default)]
+    pub volume: u64,
     pub open_interest: f64,
     pub bid: f64,
     pub ask: f64,
     pub contract_size: String,
     pub expiration: i64,
-    pub last_trade_date: i64,
+    #[serde(with = "time::serde::timestamp")]
+    pub last_trade_date: OffsetDateTime,
     pub implied_volatility: f64,
     pub in_the_money: bool,
 }
 
+/// The option chains of all the expirations of an underlying
+#[derive(Debug, Clone)]
+pub struct OptionSurface {
+    pub symbol: String,
+    /// The quote of the underlying when the expirations were retrieved
+    pub quote: Option<QuoteSnapshot>,
+    chains: Vec<OptionChain>,
+    /// The positions of the contracts in the chains by expiry, strike in thousandths and type
+    index: HashMap<(Date, i64, OptionType), (usize, usize)>,
+}
+
+impl OptionSurface {
+    pub(crate) fn new(
+        symbol: &str,
+        quote: Option<QuoteSnapshot>,
+        mut chains: Vec<OptionChain>,
+    ) -> Self {
+        chains.sort_by_key(|c| c.expiration_date);
+        let mut index = HashMap::new();
+        for (i, chain) in chains.iter().enumerate() {
+            let expiry = match expiry_date(chain.expiration_date) {
+                Some(expiry) => expiry,
+                None => continue,
+            };
+            for (option_type, contracts) in [
+                (OptionType::Call, &chain.calls),
+                (OptionType::Put, &chain.puts),
+            ] {
+                for (j, contract) in contracts.iter().enumerate() {
+                    index
+                        .entry((expiry, strike_key(contract.strike), option_type))
+                        .or_insert((i, j));
+                }
+            }
+        }
+        OptionSurface {
+            symbol: symbol.to_string(),
+            quote,
+            chains,
+            index,
+        }
+    }
+
+    /// The option chains, ordered by expiration date
+    pub fn chains(&self) -> &[OptionChain] {
+        &self.chains
+    }
+
+    /// The expiration dates, in ascending order
+    pub fn expiries(&self) -> impl Iterator<Item = Date> + '_ {
+        self.chains
+            .iter()
+            .filter_map(|c| expiry_date(c.expiration_date))
+    }
+
+    /// The contract of the expiration date, strike and type, if any
+    ///
+    /// The strikes are compared in thousandths, the precision of the OCC symbols.
+    pub fn get(
+        &self,
+        expiry: Date,
+        strike: f64,
+        option_type: OptionType,
+    ) -> Option<&OptionContract> {
+        let (i, j) = *self.index.get(&(expiry, strike_key(strike), option_type))?;
+        let chain = &self.chains[i];
+        match option_type {
+            OptionType::Call => chain.calls.get(j),
+            OptionType::Put => chain.puts.get(j),
+        }
+    }
+
+    /// All the contracts with their expiration date, strike and type
+    pub fn iter(&self) -> impl Iterator<Item = (Date, f64, OptionType, &OptionContract)> + '_ {
+        self.chains.iter().flat_map(|chain| {
+            let expiry = expiry_date(chain.expiration_date);
+            let calls = chain.calls.iter().map(|c| (OptionType::Call, c));
+            let puts = chain.puts.iter().map(|c| (OptionType::Put, c));
+            calls
+                .chain(puts)
+                .filter_map(move |(option_type, c)| Some((expiry?, c.strike, option_type, c)))
+        })
+    }
+}
+
+/// The strike in thousandths, like in the OCC symbols
+fn strike_key(strike: f64) -> i64 {
+    (strike * 1000.0).round() as i64
+}
+
+/// Yahoo! finance reports the expiration dates at midnight UTC
+fn expiry_date(timestamp: i64) -> Option<Date> {
+    OffsetDateTime::from_unix_timestamp(timestamp)
+        .ok()
+        .map(|t| t.date())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+/// An option symbol in the format of the Options Clearing Corporation: the underlying symbol
+/// followed by the expiry as `YYMMDD`, `C` or `P` and the strike price times 1000 on 8 digits
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccSymbol {
+    pub underlying: String,
+    pub expiry: Date,
+    pub option_type: OptionType,
+    pub strike: f64,
+}
+
+impl FromStr for OccSymbol {
+    type Err = YahooError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || YahooError::InvalidParameter(format!("invalid OCC symbol '{}'", s));
+
+        // the underlying is followed by 15 characters
+        if !s.is_ascii() || s.len() < 16 {
+            return Err(invalid());
+        }
+        let (underlying, rest) = s.split_at(s.len() - 15);
+        let (expiry, rest) = rest.split_at(6);
+        let (option_type, strike) = rest.split_at(1);
+
+        let number = |digits: &str| -> Result<u32, YahooError> {
+            if digits.bytes().all(|b| b.is_ascii_digit()) {
+                digits.parse().map_err(|_| invalid())
+            } else {
+                Err(invalid())
+            }
+        };
+        let month = Month::try_from(number(&expiry[2..4])? as u8).map_err(|_| invalid())?;
+        let expiry = Date::from_calendar_date(
+            2000 + number(&expiry[0..2])? as i32,
+            month,
+            number(&expiry[4..6])? as u8,
+        )
+        .map_err(|_| invalid())?;
+        let option_type = match option_type {
+            "C" => OptionType::Call,
+            "P" => OptionType::Put,
+            _ => return Err(invalid()),
+        };
+
+        Ok(OccSymbol {
+            underlying: underlying.trim_end().to_string(),
+            expiry,
+            option_type,
+            strike: number(strike)? as f64 / 1000.0,
+        })
+    }
+}
+
+impl fmt::Display for OccSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{:02}{:02}{:02}{}{:08}",
+            self.underlying,
+            self.expiry.year() % 100,
+            self.expiry.month() as u8,
+            self.expiry.day(),
+            match self.option_type {
+                OptionType::Call => "C",
+                OptionType::Put => "P",
+            },
+            (self.strike * 1000.0).round() as u64
+        )
+    }
+}
+
+/// Selects the contracts of option chains.
+///
+/// The default query selects all the contracts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OptionChainQuery {
+    first_expiry: Option<Date>,
+    last_expiry: Option<Date>,
+    spot: Option<f64>,
+    band: Option<f64>,
+    min_open_interest: Option<u64>,
+    option_type: Option<OptionType>,
+    in_the_money: Option<bool>,
+    straddle: bool,
+}
+
+impl OptionChainQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the contracts expiring from `first` to `last`, both inclusive
+    pub fn expiries(mut self, first: Date, last: Date) -> Self {
+        self.first_expiry = Some(first);
+        self.last_expiry = Some(last);
+        self
+    }
+
+    /// Select the strikes within the band around the price of the underlying, e.g. a band of
+    /// `0.1` selects the strikes from 90% to 110% of the spot price
+    ///
+    /// Unless it is given with [`OptionChainQuery::spot`], the spot price is the market price
+    /// of the underlying retrieved with the options. The strikes are not selected by
+    /// [`OptionChainQuery::apply`] as long as the spot price is unknown.
+    pub fn moneyness(mut self, band: f64) -> Self {
+        self.band = Some(band);
+        self
+    }
+
+    /// Set the price of the underlying the moneyness band is centered on
+    pub fn spot(mut self, spot: f64) -> Self {
+        self.spot = Some(spot);
+        self
+    }
+
+    /// Select the contracts with at least `open_interest` open contracts
+    pub fn min_open_interest(mut self, open_interest: u64) -> Self {
+        self.min_open_interest = Some(open_interest);
+        self
+    }
+
+    /// Select either the calls or the puts
+    pub fn option_type(mut self, option_type: OptionType) -> Self {
+        self.option_type = Some(option_type);
+        self
+    }
+
+    /// Select the contracts which are in or out of the money
+    pub fn in_the_money(mut self, in_the_money: bool) -> Self {
+        self.in_the_money = Some(in_the_money);
+        self
+    }
+
+    /// Select the strikes of which both the call and the put match all the criteria but the
+    /// type, requesting the option chains in the `straddle` format of yahoo! finance
+    pub fn straddle(mut self, straddle: bool) -> Self {
+        self.straddle = straddle;
+        self
+    }
+
+    /// The range of the strikes, `None` if all the strikes are selected
+    pub fn strike_range(&self) -> Option<(f64, f64)> {
+        let band = self.band?;
+        let spot = self.spot?;
+        Some((spot * (1.0 - band), spot * (1.0 + band)))
+    }
+
+    /// Whether the moneyness band still needs the spot price
+    pub(crate) fn needs_spot(&self) -> bool {
+        self.band.is_some() && self.spot.is_none()
+    }
+
+    /// Whether the query selects contracts of the expiration date, given as a unix timestamp
+    pub fn matches_expiry(&self, expiration_date: i64) -> bool {
+        let Some(expiry) = expiry_date(expiration_date) else {
+            return false;
+        };
+        self.first_expiry.is_none_or(|first| expiry >= first)
+            && self.last_expiry.is_none_or(|last| expiry <= last)
+    }
+
+    pub fn matches(&self, contract: &OptionContract, option_type: OptionType) -> bool {
+        self.matches_contract(contract) && self.option_type.is_none_or(|t| t == option_type)
+    }
+
+    /// Whether the contract is selected by all the criteria but its type
+    fn matches_contract(&self, contract: &OptionContract) -> bool {
+        self.matches_expiry(contract.expiration)
+            && self
+                .strike_range()
+                .is_none_or(|(min, max)| contract.strike >= min && contract.strike <= max)
+            && self
+                .min_open_interest
+                .is_none_or(|min| contract.open_interest >= min as f64)
+            && self
+                .in_the_money
+                .is_none_or(|itm| contract.in_the_money == itm)
+    }
+
+    /// The option chain with the selected contracts only
+    pub fn apply(&self, chain: &OptionChain) -> OptionChain {
+        let select = |contracts: &[OptionContract]| {
+            contracts
+                .iter()
+                .filter(|c| self.matches_contract(c))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let mut calls = select(&chain.calls);
+        let mut puts = select(&chain.puts);
+        // the straddles are paired before selecting the type
+        if self.straddle {
+            let strikes = |contracts: &[OptionContract]| {
+                contracts
+                    .iter()
+                    .map(|c| strike_key(c.strike))
+                    .collect::<HashSet<_>>()
+            };
+            let call_strikes = strikes(&calls);
+            let put_strikes = strikes(&puts);
+            calls.retain(|c| put_strikes.contains(&strike_key(c.strike)));
+            puts.retain(|c| call_strikes.contains(&strike_key(c.strike)));
+        }
+        match self.option_type {
+            Some(OptionType::Call) => puts.clear(),
+            Some(OptionType::Put) => calls.clear(),
+            None => {}
+        }
+        OptionChain {
+            expiration_date: chain.expiration_date,
+            calls,
+            puts,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1861,5 +2238,207 @@ mod test {
         assert_eq!(1733443200, option_chain.expiration_date);
         assert_eq!(47, option_chain.calls.len());
         assert_eq!(46, option_chain.puts.len());
+
+        let call = &option_chain.calls[1];
+        assert_eq!(call.contract_symbol, "AAPL241206C00125000");
+        assert_eq!(call.volume, 2);
+        assert_eq!(call.last_trade_date.unix_timestamp(), 1732299649);
+        assert_eq!(
+            call.occ,
+            Some(OccSymbol {
+                underlying: "AAPL".to_string(),
+                expiry: time::macros::date!(2024 - 12 - 06),
+                option_type: OptionType::Call,
+                strike: 125.0,
+            })
+        );
+        // the volume is missing if no contract was traded
+        assert_eq!(option_chain.calls[0].volume, 0);
+        assert!(option_chain
+            .puts
+            .iter()
+            .all(|p| p.occ.as_ref().unwrap().option_type == OptionType::Put));
+    }
+
+    #[test]
+    fn test_option_surface() {
+        let options = options_from_response(serde_json::from_str(OPTIONS_OUTPUT).unwrap()).unwrap();
+        assert_eq!(
+            options.quote.as_ref().map(|q| q.symbol.as_str()),
+            Some("AAPL")
+        );
+        let chain =
+            option_chain_from_response(serde_json::from_str(OPTIONS_OUTPUT).unwrap()).unwrap();
+        let mut later = chain.clone();
+        later.expiration_date += 7 * 86400;
+        later.puts.clear();
+
+        let surface = OptionSurface::new("AAPL", options.quote, vec![later, chain]);
+        assert_eq!(
+            surface.expiries().collect::<Vec<_>>(),
+            vec![
+                time::macros::date!(2024 - 12 - 06),
+                time::macros::date!(2024 - 12 - 13)
+            ]
+        );
+        let put = surface
+            .get(time::macros::date!(2024 - 12 - 06), 125.0, OptionType::Put)
+            .unwrap();
+        assert_eq!(put.contract_symbol, "AAPL241206P00125000");
+        let put = surface
+            .get(
+                time::macros::date!(2024 - 12 - 06),
+                125.0 * (1.0 + 1e-12),
+                OptionType::Put,
+            )
+            .unwrap();
+        assert_eq!(put.contract_symbol, "AAPL241206P00125000");
+        assert!(surface
+            .get(time::macros::date!(2024 - 12 - 13), 125.0, OptionType::Put)
+            .is_none());
+        assert!(surface
+            .get(time::macros::date!(2024 - 12 - 06), 125.5, OptionType::Call)
+            .is_none());
+        assert_eq!(surface.iter().count(), 47 + 46 + 47);
+        assert!(surface
+            .iter()
+            .filter(|(expiry, ..)| *expiry == time::macros::date!(2024 - 12 - 13))
+            .all(
+                |(_, strike, option_type, c)| option_type == OptionType::Call && c.strike == strike
+            ));
+    }
+
+    #[test]
+    fn test_compose_option_chain_url() {
+        let date = time::macros::datetime!(2024-12-06 0:00 UTC);
+        assert_eq!(
+            compose_option_chain_url(
+                "https://query2.finance.yahoo.com/v7/finance/options",
+                "AAPL",
+                date,
+                &OptionChainQuery::new()
+            ),
+            "https://query2.finance.yahoo.com/v7/finance/options/AAPL?date=1733443200"
+        );
+        assert_eq!(
+            compose_option_chain_url(
+                "https://query2.finance.yahoo.com/v7/finance/options", "AAPL", date, &OptionChainQuery::new().moneyness(0.25).spot(200.0)),
+            "https://query2.finance.yahoo.com/v7/finance/options/AAPL?date=1733443200&strikeMin=150&strikeMax=250"
+        );
+        assert_eq!(
+            compose_option_chain_url(
+                "https://query2.finance.yahoo.com/v7/finance/options", "AAPL", date, &OptionChainQuery::new().moneyness(0.1).straddle(true)),
+            "https://query2.finance.yahoo.com/v7/finance/options/AAPL?date=1733443200&straddle=true"
+        );
+    }
+
+    #[test]
+    fn test_option_chain_query() {
+        let chain =
+            option_chain_from_response(serde_json::from_str(OPTIONS_OUTPUT).unwrap()).unwrap();
+        let strikes = |query: OptionChainQuery| {
+            let chain = query.apply(&chain);
+            let strikes = |contracts: &[OptionContract]| {
+                contracts.iter().map(|c| c.strike).collect::<Vec<_>>()
+            };
+            (strikes(&chain.calls), strikes(&chain.puts))
+        };
+
+        let (calls, puts) = strikes(OptionChainQuery::new());
+        assert_eq!((calls.len(), puts.len()), (47, 46));
+
+        let (calls, puts) = strikes(OptionChainQuery::new().moneyness(0.025).spot(230.0));
+        assert_eq!(calls, vec![225.0, 227.5, 230.0, 232.5, 235.0]);
+        assert_eq!(puts, calls);
+
+        // the strikes are not selected without the spot price
+        let (calls, puts) = strikes(OptionChainQuery::new().moneyness(0.025));
+        assert_eq!((calls.len(), puts.len()), (47, 46));
+
+        let (calls, puts) = strikes(OptionChainQuery::new().straddle(true));
+        assert_eq!((calls.len(), puts.len()), (43, 43));
+        assert_eq!(puts, calls);
+
+        let (calls, puts) = strikes(
+            OptionChainQuery::new()
+                .straddle(true)
+                .option_type(OptionType::Put),
+        );
+        assert!(calls.is_empty());
+        assert_eq!(puts.len(), 43);
+
+        let (calls, puts) = strikes(
+            OptionChainQuery::new()
+                .moneyness(0.025)
+                .spot(230.0)
+                .option_type(OptionType::Put)
+                .in_the_money(true),
+        );
+        assert!(calls.is_empty());
+        assert_eq!(puts, vec![230.0, 232.5, 235.0]);
+
+        let mut traded = chain.clone();
+        traded.calls[10].open_interest = 150.0;
+        traded.calls[11].open_interest = 99.0;
+        let selected = OptionChainQuery::new()
+            .min_open_interest(100)
+            .apply(&traded);
+        assert_eq!(selected.calls.len(), 1);
+        assert_eq!(selected.calls[0].strike, traded.calls[10].strike);
+        assert!(selected.puts.is_empty());
+
+        let december = |first, last| {
+            OptionChainQuery::new().expiries(
+                time::Date::from_calendar_date(2024, Month::December, first).unwrap(),
+                time::Date::from_calendar_date(2024, Month::December, last).unwrap(),
+            )
+        };
+        assert!(december(6, 6).matches_expiry(chain.expiration_date));
+        assert!(!december(7, 31).matches_expiry(chain.expiration_date));
+        assert_eq!(strikes(december(1, 6)).0.len(), 47);
+        assert!(strikes(december(7, 31)).0.is_empty());
+    }
+
+    #[test]
+    fn test_option_chain_straddles() {
+        let mut response: Value = serde_json::from_str(OPTIONS_OUTPUT).unwrap();
+        let options = &mut response["optionChain"]["result"][0]["options"][0];
+        let calls = options["calls"].take();
+        let puts = options["puts"].take();
+        let straddles: Vec<Value> = (0..2)
+            .map(|i| serde_json::json!({"strike": calls[i]["strike"], "call": calls[i], "put": puts[i]}))
+            .chain(std::iter::once(serde_json::json!({"strike": 400.0, "call": calls[46]})))
+            .collect();
+        *options = serde_json::json!({"expirationDate": 1733443200, "straddles": straddles});
+
+        let chain = option_chain_from_response(response).unwrap();
+        assert_eq!(chain.expiration_date, 1733443200);
+        assert_eq!(chain.calls.len(), 3);
+        assert_eq!(chain.puts.len(), 2);
+        assert_eq!(chain.calls[0].strike, chain.puts[0].strike);
+        assert_eq!(
+            chain.puts[1].occ.as_ref().map(|occ| occ.option_type),
+            Some(OptionType::Put)
+        );
+    }
+
+    #[test]
+    fn test_occ_symbol() {
+        let symbol: OccSymbol = "SPXW251219P05912500".parse().unwrap();
+        assert_eq!(symbol.underlying, "SPXW");
+        assert_eq!(symbol.expiry, time::macros::date!(2025 - 12 - 19));
+        assert_eq!(symbol.option_type, OptionType::Put);
+        assert_eq!(symbol.strike, 5912.5);
+        assert_eq!(symbol.to_string(), "SPXW251219P05912500");
+
+        let symbol: OccSymbol = "F250117C00012500".parse().unwrap();
+        assert_eq!(symbol.underlying, "F");
+        assert_eq!(symbol.strike, 12.5);
+
+        assert!("AAPL241206X00100000".parse::<OccSymbol>().is_err());
+        assert!("AAPL241306C00100000".parse::<OccSymbol>().is_err());
+        assert!("AAPL2412C00100000".parse::<OccSymbol>().is_err());
+        assert!("241206C00100000".parse::<OccSymbol>().is_err());
+        assert!("AAPL241206C0010000€".parse::<OccSymbol>().is_err());
     }
 }
@@ -0,0 +1,364 @@
+use std::convert::TryFrom;
+
+use time::{Date, Month, OffsetDateTime};
+
+use crate::quote_summary::{
+    InsiderHolder, MajorHoldersBreakdown, NetSharePurchaseActivity, OwnershipEntry, QuoteSummary,
+};
+use crate::YahooError;
+
+/// The holders of a symbol and the trades of its insiders
+#[derive(Debug, Clone)]
+pub struct OwnershipInfo {
+    pub major_holders: Option<MajorHoldersBreakdown>,
+    /// The institutions holding the most shares
+    pub institutions: Vec<OwnershipEntry>,
+    /// The funds holding the most shares
+    pub funds: Vec<OwnershipEntry>,
+    pub insider_holders: Vec<InsiderHolder>,
+    /// The trades of the insiders, latest first
+    pub insider_trades: Vec<InsiderTrade>,
+    /// The insider purchases and sales summarized by yahoo! finance, usually over six months
+    pub net_share_purchase_activity: Option<NetSharePurchaseActivity>,
+}
+
+/// The kind of an insider trade, derived from its description
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InsiderTradeKind {
+    Purchase,
+    Sale,
+    /// A grant or an award of shares
+    Award,
+    /// The exercise or the conversion of a derivative security
+    Exercise,
+    Gift,
+    /// A trade yahoo! finance doesn't describe, or describes in a way not known to this crate
+    Other,
+}
+
+impl InsiderTradeKind {
+    fn from_text(text: &str) -> Self {
+        let text = text.to_lowercase();
+        if text.starts_with("purchase") {
+            InsiderTradeKind::Purchase
+        } else if text.starts_with("sale") {
+            InsiderTradeKind::Sale
+        } else if text.contains("award") || text.contains("grant") {
+            InsiderTradeKind::Award
+        } else if text.contains("exercise") || text.contains("conversion") {
+            InsiderTradeKind::Exercise
+        } else if text.contains("gift") {
+            InsiderTradeKind::Gift
+        } else {
+            InsiderTradeKind::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsiderTrade {
+    pub filer_name: Option<String>,
+    pub filer_relation: Option<String>,
+    pub date: Date,
+    pub kind: InsiderTradeKind,
+    pub shares: f64,
+    /// The value of the trade, missing for trades without a price
+    pub value: Option<f64>,
+    /// Whether the insider owns the shares directly or indirectly, e.g. through a trust
+    pub is_direct: Option<bool>,
+    pub text: Option<String>,
+}
+
+impl InsiderTrade {
+    /// The average price per share
+    pub fn price(&self) -> Option<f64> {
+        match self.value {
+            Some(value) if self.shares > 0.0 => Some(value / self.shares),
+            _ => None,
+        }
+    }
+}
+
+/// The purchases and the sales of the insiders during a period
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InsiderActivity {
+    pub purchases: usize,
+    pub sales: usize,
+    pub shares_bought: f64,
+    pub shares_sold: f64,
+}
+
+impl InsiderActivity {
+    /// The shares bought minus the shares sold
+    pub fn net_shares(&self) -> f64 {
+        self.shares_bought - self.shares_sold
+    }
+}
+
+impl OwnershipInfo {
+    /// Collect the ownership data from the `majorHoldersBreakdown`, `institutionOwnership`,
+    /// `fundOwnership`, `insiderHolders`, `insiderTransactions` and `netSharePurchaseActivity`
+    /// modules of the quote summary.
+    ///
+    /// Insider transactions without a date or a number of shares are left out.
+    pub(crate) fn from_summary(summary: QuoteSummary) -> Result<Self, YahooError> {
+        if summary.major_holders_breakdown.is_none()
+            && summary.institution_ownership.is_none()
+            && summary.fund_ownership.is_none()
+            && summary.insider_holders.is_none()
+            && summary.insider_transactions.is_none()
+            && summary.net_share_purchase_activity.is_none()
+        {
+            return Err(YahooError::EmptyDataSet);
+        }
+
+        let mut insider_trades = vec![];
+        for transaction in summary
+            .insider_transactions
+            .and_then(|t| t.transactions)
+            .unwrap_or_default()
+        {
+            let (Some(date), Some(shares)) = (transaction.start_date, transaction.shares) else {
+                continue;
+            };
+            let date = OffsetDateTime::from_unix_timestamp(date)
+                .map_err(|_| YahooError::DataInconsistency)?
+                .date();
+            let text = transaction.transaction_text.filter(|t| !t.is_empty());
+            insider_trades.push(InsiderTrade {
+                filer_name: transaction.filer_name,
+                filer_relation: transaction.filer_relation,
+                date,
+                kind: InsiderTradeKind::from_text(text.as_deref().unwrap_or_default()),
+                shares,
+                value: transaction.value,
+                is_direct: match transaction.ownership.as_deref() {
+                    Some("D") => Some(true),
+                    Some("I") => Some(false),
+                    _ => None,
+                },
+                text,
+            });
+        }
+        insider_trades.sort_by_key(|t| std::cmp::Reverse(t.date));
+
+        Ok(OwnershipInfo {
+            major_holders: summary.major_holders_breakdown,
+            institutions: summary
+                .institution_ownership
+                .and_then(|o| o.ownership_list)
+                .unwrap_or_default(),
+            funds: summary
+                .fund_ownership
+                .and_then(|o| o.ownership_list)
+                .unwrap_or_default(),
+            insider_holders: summary
+                .insider_holders
+                .and_then(|h| h.holders)
+                .unwrap_or_default(),
+            insider_trades,
+            net_share_purchase_activity: summary.net_share_purchase_activity,
+        })
+    }
+
+    /// Summarize the insider purchases and sales from the given date on.
+    ///
+    /// Only the open market purchases and sales are counted, not the awards, the exercises
+    /// or the gifts.
+    pub fn insider_activity_since(&self, since: Date) -> InsiderActivity {
+        let mut activity = InsiderActivity::default();
+        for trade in self.insider_trades.iter().filter(|t| t.date >= since) {
+            match trade.kind {
+                InsiderTradeKind::Purchase => {
+                    activity.purchases += 1;
+                    activity.shares_bought += trade.shares;
+                }
+                InsiderTradeKind::Sale => {
+                    activity.sales += 1;
+                    activity.shares_sold += trade.shares;
+                }
+                _ => {}
+            }
+        }
+        activity
+    }
+
+    /// Summarize the insider purchases and sales of the last `months` months.
+    pub fn insider_activity_last_months(&self, months: u32) -> InsiderActivity {
+        let today = OffsetDateTime::now_utc().date();
+        self.insider_activity_since(months_before(today, months))
+    }
+
+    /// The fraction of the shares held by the `n` largest institutional holders
+    pub fn top_institutions_pct_held(&self, n: usize) -> f64 {
+        let mut held: Vec<f64> = self
+            .institutions
+            .iter()
+            .filter_map(|i| i.pct_held)
+            .collect();
+        held.sort_by(|a, b| b.total_cmp(a));
+        held.iter().take(n).sum()
+    }
+}
+
+/// The same day `months` months before, or the last day of that month if it is shorter.
+///
+/// The earliest representable date is returned if that month is out of range.
+fn months_before(date: Date, months: u32) -> Date {
+    let total = i32::try_from(months)
+        .ok()
+        .and_then(|months| (date.year() * 12 + date.month() as i32 - 1).checked_sub(months));
+    total
+        .and_then(|total| {
+            let year = total.div_euclid(12);
+            let month = Month::try_from(total.rem_euclid(12) as u8 + 1).ok()?;
+            let day = date.day().min(month.length(year));
+            Date::from_calendar_date(year, month, day).ok()
+        })
+        .unwrap_or(Date::MIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+    use crate::quote_summary::from_response;
+
+    const OWNERSHIP: &str = r#"
+    {
+        "quoteSummary": {
+            "result": [
+                {
+                    "majorHoldersBreakdown": {
+                        "maxAge": 1,
+                        "insidersPercentHeld": 0.02703,
+                        "institutionsPercentHeld": 0.61514,
+                        "institutionsFloatPercentHeld": 0.6322,
+                        "institutionsCount": 6659
+                    },
+                    "institutionOwnership": {
+                        "maxAge": 1,
+                        "ownershipList": [
+                            {"maxAge": 1, "reportDate": 1719705600, "organization": "Blackrock Inc.", "pctHeld": 0.0673, "position": 1023000000, "value": 242000000000},
+                            {"maxAge": 1, "reportDate": 1719705600, "organization": "Vanguard Group Inc", "pctHeld": 0.0876, "position": 1331903760, "value": 315489713071},
+                            {"maxAge": 1, "reportDate": 1719705600, "organization": "State Street Corporation", "pctHeld": 0.0371, "position": 564000000, "value": 133000000000}
+                        ]
+                    },
+                    "fundOwnership": {
+                        "maxAge": 1,
+                        "ownershipList": [
+                            {"maxAge": 1, "reportDate": 1719705600, "organization": "Vanguard Total Stock Market Index Fund", "pctHeld": 0.0296, "position": 450023542, "value": 106596576533}
+                        ]
+                    },
+                    "insiderHolders": {
+                        "holders": [
+                            {"maxAge": 1, "name": "COOK TIMOTHY D", "relation": "Chief Executive Officer", "url": "", "transactionDescription": "Sale", "latestTransDate": 1712102400, "positionDirect": 3280180, "positionDirectDate": 1712102400}
+                        ],
+                        "maxAge": 1
+                    },
+                    "insiderTransactions": {
+                        "transactions": [
+                            {"maxAge": 1, "shares": 4130, "value": 942776, "filerUrl": "", "transactionText": "Sale at price 228.28 per share.", "filerName": "ADAMS KATHERINE L", "filerRelation": "General Counsel", "moneyText": "", "startDate": 1730419200, "ownership": "D"},
+                            {"maxAge": 1, "shares": 1000, "value": 220000, "filerUrl": "", "transactionText": "Purchase at price 220.00 per share.", "filerName": "LEVINSON ARTHUR D", "filerRelation": "Director", "moneyText": "", "startDate": 1727740800, "ownership": "I"},
+                            {"maxAge": 1, "shares": 8570, "filerUrl": "", "transactionText": "Stock Award(Grant) at price 0.00 per share.", "filerName": "ADAMS KATHERINE L", "filerRelation": "General Counsel", "moneyText": "", "startDate": 1730332800, "ownership": "D"},
+                            {"maxAge": 1, "shares": 500, "value": 85000, "filerUrl": "", "transactionText": "Sale at price 170.00 per share.", "filerName": "COOK TIMOTHY D", "filerRelation": "Chief Executive Officer", "moneyText": "", "startDate": 1704067200, "ownership": "D"},
+                            {"maxAge": 1, "filerUrl": "", "transactionText": "", "filerName": "O'BRIEN DEIRDRE", "filerRelation": "Officer", "moneyText": "", "startDate": 1704067200, "ownership": "D"}
+                        ],
+                        "maxAge": 1
+                    },
+                    "netSharePurchaseActivity": {
+                        "maxAge": 1,
+                        "period": "6m",
+                        "buyInfoCount": 8,
+                        "buyInfoShares": 520454,
+                        "sellInfoCount": 20,
+                        "sellInfoShares": 2086283,
+                        "netInfoShares": -1565829,
+                        "totalInsiderShares": 72618426
+                    }
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    #[test]
+    fn test_from_summary() {
+        let summary = from_response(serde_json::from_str(OWNERSHIP).unwrap()).unwrap();
+        let info = OwnershipInfo::from_summary(summary).unwrap();
+
+        assert_eq!(info.major_holders.unwrap().institutions_count, Some(6659));
+        assert_eq!(info.institutions.len(), 3);
+        assert_eq!(info.funds.len(), 1);
+        assert_eq!(
+            info.insider_holders[0].name.as_deref(),
+            Some("COOK TIMOTHY D")
+        );
+        assert_eq!(
+            info.net_share_purchase_activity.unwrap().net_info_shares,
+            Some(-1565829.0)
+        );
+
+        // the transaction without shares is left out, the others are ordered latest first
+        let trades = &info.insider_trades;
+        assert_eq!(trades.len(), 4);
+        assert_eq!(trades[0].date, date!(2024 - 11 - 01));
+        assert_eq!(trades[0].kind, InsiderTradeKind::Sale);
+        assert_eq!(trades[0].is_direct, Some(true));
+        assert!((trades[0].price().unwrap() - 228.28).abs() < 0.01);
+        assert_eq!(trades[1].kind, InsiderTradeKind::Award);
+        assert_eq!(trades[1].price(), None);
+        assert_eq!(trades[2].kind, InsiderTradeKind::Purchase);
+        assert_eq!(trades[2].is_direct, Some(false));
+        assert_eq!(trades[3].date, date!(2024 - 01 - 01));
+    }
+
+    #[test]
+    fn test_aggregation() {
+        let summary = from_response(serde_json::from_str(OWNERSHIP).unwrap()).unwrap();
+        let info = OwnershipInfo::from_summary(summary).unwrap();
+
+        let activity = info.insider_activity_since(date!(2024 - 09 - 01));
+        assert_eq!(activity.purchases, 1);
+        assert_eq!(activity.sales, 1);
+        assert_eq!(activity.shares_bought, 1000.0);
+        assert_eq!(activity.shares_sold, 4130.0);
+        assert_eq!(activity.net_shares(), -3130.0);
+
+        let activity = info.insider_activity_since(date!(2024 - 01 - 01));
+        assert_eq!(activity.sales, 2);
+        assert_eq!(activity.net_shares(), -3630.0);
+
+        let activity = info.insider_activity_last_months(200_000);
+        assert_eq!(activity.sales, 2);
+
+        let top = info.top_institutions_pct_held(2);
+        assert!((top - (0.0876 + 0.0673)).abs() < 1e-12);
+        let all = info.top_institutions_pct_held(10);
+        assert!((all - (0.0876 + 0.0673 + 0.0371)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_months_before() {
+        assert_eq!(
+            months_before(date!(2024 - 11 - 15), 6),
+            date!(2024 - 05 - 15)
+        );
+        assert_eq!(
+            months_before(date!(2024 - 03 - 31), 1),
+            date!(2024 - 02 - 29)
+        );
+        assert_eq!(
+            months_before(date!(2024 - 02 - 10), 14),
+            date!(2022 - 12 - 10)
+        );
+        assert_eq!(
+            months_before(date!(2024 - 02 - 10), 0),
+            date!(2024 - 02 - 10)
+        );
+        assert_eq!(months_before(date!(2024 - 02 - 10), 200_000), Date::MIN);
+        assert_eq!(months_before(date!(2024 - 02 - 10), u32::MAX), Date::MIN);
+    }
+}
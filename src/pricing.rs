@@ -0,0 +1,418 @@
+//! Black-Scholes pricing of European options.
+//!
+//! The rates and the volatility are annual and continuously compounded, the time is in years of
+//! 365 days.
+
+use time::OffsetDateTime;
+
+use crate::options::{OptionContract, OptionType};
+use crate::YahooError;
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+const MIN_VOLATILITY: f64 = 1e-6;
+const MAX_VOLATILITY: f64 = 10.0;
+const MAX_ITERATIONS: usize = 100;
+const PRICE_TOLERANCE: f64 = 1e-10;
+const VOLATILITY_TOLERANCE: f64 = 1e-10;
+
+/// The market conditions to price options of an underlying
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Market {
+    pub underlying_price: f64,
+    /// The risk-free interest rate, e.g. `0.045` for 4.5%
+    pub rate: f64,
+    /// The dividend yield of the underlying, e.g. `0.005` for 0.5%
+    pub dividend_yield: f64,
+}
+
+/// The sensitivities of the price of an option
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    /// The change of the price per change of the underlying price
+    pub delta: f64,
+    /// The change of the delta per change of the underlying price
+    pub gamma: f64,
+    /// The change of the price per change of the volatility by 1.0, i.e. 100 percentage points
+    pub vega: f64,
+    /// The change of the price per year, divide by 365 for the daily time decay
+    pub theta: f64,
+    /// The change of the price per change of the rate by 1.0, i.e. 100 percentage points
+    pub rho: f64,
+}
+
+/// A European option in the Black-Scholes model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackScholes {
+    pub option_type: OptionType,
+    pub strike: f64,
+    /// The time to expiry in years
+    pub time: f64,
+    pub market: Market,
+    pub volatility: f64,
+}
+
+impl BlackScholes {
+    /// The option with the volatility yahoo! finance reports for the contract, at the time `at`
+    pub fn from_contract(
+        contract: &OptionContract,
+        market: &Market,
+        at: OffsetDateTime,
+    ) -> Result<Self, YahooError> {
+        let occ = contract.occ.as_ref().ok_or_else(|| {
+            YahooError::InvalidParameter(format!(
+                "unknown option type of contract {}",
+                contract.contract_symbol
+            ))
+        })?;
+        Ok(BlackScholes {
+            option_type: occ.option_type,
+            strike: contract.strike,
+            time: time_to_expiry(contract, at)?,
+            market: *market,
+            volatility: contract.implied_volatility,
+        })
+    }
+
+    pub fn price(&self) -> f64 {
+        let (d1, d2) = self.d1_d2();
+        let spot = self.spot_discount() * self.market.underlying_price;
+        let strike = self.strike_discount() * self.strike;
+        match self.option_type {
+            OptionType::Call => spot * cdf(d1) - strike * cdf(d2),
+            OptionType::Put => strike * cdf(-d2) - spot * cdf(-d1),
+        }
+    }
+
+    pub fn greeks(&self) -> Greeks {
+        let (d1, d2) = self.d1_d2();
+        let sqrt_time = self.time.sqrt();
+        let spot = self.spot_discount() * self.market.underlying_price;
+        let strike = self.strike_discount() * self.strike;
+        let Market {
+            rate,
+            dividend_yield,
+            ..
+        } = self.market;
+
+        let gamma = self.spot_discount() * pdf(d1)
+            / (self.market.underlying_price * self.volatility * sqrt_time);
+        let vega = spot * pdf(d1) * sqrt_time;
+        let decay = -spot * pdf(d1) * self.volatility / (2.0 * sqrt_time);
+        match self.option_type {
+            OptionType::Call => Greeks {
+                delta: self.spot_discount() * cdf(d1),
+                gamma,
+                vega,
+                theta: decay - rate * strike * cdf(d2) + dividend_yield * spot * cdf(d1),
+                rho: self.time * strike * cdf(d2),
+            },
+            OptionType::Put => Greeks {
+                delta: -self.spot_discount() * cdf(-d1),
+                gamma,
+                vega,
+                theta: decay + rate * strike * cdf(-d2) - dividend_yield * spot * cdf(-d1),
+                rho: -self.time * strike * cdf(-d2),
+            },
+        }
+    }
+
+    /// The volatility at which the option is worth `price`
+    pub fn implied_volatility(&self, price: f64) -> Result<f64, YahooError> {
+        let spot = self.spot_discount() * self.market.underlying_price;
+        let strike = self.strike_discount() * self.strike;
+        let (lower, upper) = match self.option_type {
+            OptionType::Call => ((spot - strike).max(0.0), spot),
+            OptionType::Put => ((strike - spot).max(0.0), strike),
+        };
+        if !(price > lower && price < upper) {
+            return Err(YahooError::InvalidParameter(format!(
+                "price {} is outside of the arbitrage bounds {} to {}",
+                price, lower, upper
+            )));
+        }
+
+        // Newton's method, falling back to bisection when it leaves the bracket
+        let mut option = BlackScholes {
+            volatility: 0.3,
+            ..*self
+        };
+        let (mut low, mut high) = (MIN_VOLATILITY, MAX_VOLATILITY);
+        for _ in 0..MAX_ITERATIONS {
+            let diff = option.price() - price;
+            if diff.abs() < PRICE_TOLERANCE {
+                return Ok(option.volatility);
+            }
+            if diff > 0.0 {
+                high = option.volatility;
+            } else {
+                low = option.volatility;
+            }
+            let vega = option.greeks().vega;
+            let next = option.volatility - diff / vega;
+            option.volatility = if vega > 0.0 && next > low && next < high {
+                next
+            } else {
+                (low + high) / 2.0
+            };
+            if high - low < VOLATILITY_TOLERANCE {
+                return Ok(option.volatility);
+            }
+        }
+        Err(YahooError::InvalidParameter(format!(
+            "the implied volatility of price {} did not converge",
+            price
+        )))
+    }
+
+    fn d1_d2(&self) -> (f64, f64) {
+        let deviation = self.volatility * self.time.sqrt();
+        let d1 = ((self.market.underlying_price / self.strike).ln()
+            + (self.market.rate - self.market.dividend_yield
+                + self.volatility * self.volatility / 2.0)
+                * self.time)
+            / deviation;
+        (d1, d1 - deviation)
+    }
+
+    fn spot_discount(&self) -> f64 {
+        (-self.market.dividend_yield * self.time).exp()
+    }
+
+    fn strike_discount(&self) -> f64 {
+        (-self.market.rate * self.time).exp()
+    }
+}
+
+/// The greeks of the contract with the volatility yahoo! finance reports, at the time `at`
+pub fn greeks(
+    contract: &OptionContract,
+    market: &Market,
+    at: OffsetDateTime,
+) -> Result<Greeks, YahooError> {
+    let option = BlackScholes::from_contract(contract, market, at)?;
+    if option.volatility <= 0.0 {
+        return Err(YahooError::InvalidParameter(format!(
+            "no implied volatility for contract {}",
+            contract.contract_symbol
+        )));
+    }
+    Ok(option.greeks())
+}
+
+/// The volatility implied by the mid price between the bid and the ask of the contract, at the
+/// time `at`
+pub fn implied_volatility(
+    contract: &OptionContract,
+    market: &Market,
+    at: OffsetDateTime,
+) -> Result<f64, YahooError> {
+    if contract.bid <= 0.0 || contract.ask < contract.bid {
+        return Err(YahooError::InvalidParameter(format!(
+            "no bid and ask for contract {}",
+            contract.contract_symbol
+        )));
+    }
+    BlackScholes::from_contract(contract, market, at)?
+        .implied_volatility((contract.bid + contract.ask) / 2.0)
+}
+
+/// The time from `at` to the expiration of the contract in years.
+///
+/// Yahoo! finance reports the expiration at midnight UTC of the expiration date.
+pub fn time_to_expiry(contract: &OptionContract, at: OffsetDateTime) -> Result<f64, YahooError> {
+    let time = (contract.expiration - at.unix_timestamp()) as f64 / SECONDS_PER_YEAR;
+    if time > 0.0 {
+        Ok(time)
+    } else {
+        Err(YahooError::InvalidParameter(format!(
+            "contract {} is expired",
+            contract.contract_symbol
+        )))
+    }
+}
+
+/// The density of the standard normal distribution
+fn pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// The cumulative standard normal distribution
+fn cdf(x: f64) -> f64 {
+    erfc(-x / std::f64::consts::SQRT_2) / 2.0
+}
+
+/// The complementary error function with a relative error below 1.2e-7 (Numerical Recipes)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + z / 2.0);
+    let r = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    fn option(option_type: OptionType) -> BlackScholes {
+        BlackScholes {
+            option_type,
+            strike: 100.0,
+            time: 0.5,
+            market: Market {
+                underlying_price: 105.0,
+                rate: 0.05,
+                dividend_yield: 0.02,
+            },
+            volatility: 0.25,
+        }
+    }
+
+    fn contract(symbol: &str, bid: f64, ask: f64) -> OptionContract {
+        let json = serde_json::json!({
+            "contractSymbol": symbol,
+            "strike": 100.0,
+            "currency": "USD",
+            "lastPrice": 10.0,
+            "change": 0.0,
+            "percentChange": 0.0,
+            "openInterest": 100.0,
+            "bid": bid,
+            "ask": ask,
+            "contractSize": "REGULAR",
+            "expiration": 1750377600,
+            "lastTradeDate": 1734636000,
+            "impliedVolatility": 0.0,
+            "inTheMoney": true
+        });
+        let mut contract: OptionContract = serde_json::from_value(json).unwrap();
+        contract.occ = symbol.parse().ok();
+        contract
+    }
+
+    #[test]
+    fn test_cdf() {
+        assert_close(cdf(0.0), 0.5, 1e-7);
+        assert_close(cdf(1.0), 0.841344746, 1e-7);
+        assert_close(cdf(-1.96), 0.024997895, 1e-7);
+        assert_close(cdf(3.0) + cdf(-3.0), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_price_and_greeks() {
+        let call = option(OptionType::Call);
+        let put = option(OptionType::Put);
+        assert_close(call.price(), 10.7633, 1e-3);
+        assert_close(put.price(), 4.3391, 1e-3);
+
+        // put-call parity
+        let forward = 105.0 * (-0.02f64 * 0.5).exp() - 100.0 * (-0.05f64 * 0.5).exp();
+        assert_close(call.price() - put.price(), forward, 1e-9);
+
+        let (c, p) = (call.greeks(), put.greeks());
+        assert_close(c.delta, 0.6667, 1e-3);
+        assert_close(c.delta - p.delta, (-0.02f64 * 0.5).exp(), 1e-9);
+        assert_close(c.gamma, p.gamma, 1e-12);
+        assert_close(c.vega, p.vega, 1e-12);
+
+        // compare with finite differences
+        let bump = |f: &dyn Fn(&mut BlackScholes), option: &BlackScholes| {
+            let mut bumped = *option;
+            f(&mut bumped);
+            bumped.price() - option.price()
+        };
+        for (option, greeks) in [(call, c), (put, p)] {
+            let h = 1e-4;
+            assert_close(
+                bump(&|o| o.market.underlying_price += h, &option) / h,
+                greeks.delta,
+                1e-3,
+            );
+            assert_close(bump(&|o| o.volatility += h, &option) / h, greeks.vega, 1e-2);
+            assert_close(bump(&|o| o.time -= h, &option) / h, greeks.theta, 1e-2);
+            assert_close(bump(&|o| o.market.rate += h, &option) / h, greeks.rho, 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_implied_volatility() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            for volatility in [0.05, 0.25, 1.5] {
+                let priced = BlackScholes {
+                    volatility,
+                    ..option(option_type)
+                };
+                let implied = option(option_type)
+                    .implied_volatility(priced.price())
+                    .unwrap();
+                assert_close(implied, volatility, 1e-6);
+            }
+        }
+        // below the intrinsic value
+        assert!(option(OptionType::Call).implied_volatility(1.0).is_err());
+        assert!(option(OptionType::Put).implied_volatility(200.0).is_err());
+    }
+
+    #[test]
+    fn test_contract() {
+        let market = Market {
+            underlying_price: 105.0,
+            rate: 0.05,
+            dividend_yield: 0.02,
+        };
+        let at = datetime!(2024-12-19 19:20:00 UTC);
+        let contract = contract("XYZ250620C00100000", 10.3, 10.6);
+        assert_close(time_to_expiry(&contract, at).unwrap(), 0.5, 1e-3);
+
+        let volatility = implied_volatility(&contract, &market, at).unwrap();
+        let option = BlackScholes {
+            volatility,
+            ..BlackScholes::from_contract(&contract, &market, at).unwrap()
+        };
+        assert_close(option.price(), 10.45, 1e-8);
+
+        // yahoo! finance reports no volatility
+        assert!(greeks(&contract, &market, at).is_err());
+        let contract = OptionContract {
+            implied_volatility: volatility,
+            ..contract
+        };
+        assert_eq!(greeks(&contract, &market, at).unwrap(), option.greeks());
+
+        assert!(implied_volatility(&contract, &market, datetime!(2025-06-21 0:00 UTC)).is_err());
+        let no_bid = OptionContract {
+            bid: 0.0,
+            ..contract.clone()
+        };
+        assert!(implied_volatility(&no_bid, &market, at).is_err());
+        let unknown = OptionContract {
+            occ: None,
+            ..contract
+        };
+        assert!(implied_volatility(&unknown, &market, at).is_err());
+    }
+}
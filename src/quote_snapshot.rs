@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::YahooError;
+
+macro_rules! QUERY {
+    () => {
+        "{url}?symbols={symbols}"
+    };
+}
+
+/// The state of the market the instrument is traded on
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarketState {
+    #[serde(rename = "PREPRE")]
+    PrePre,
+    #[serde(rename = "PRE")]
+    Pre,
+    #[serde(rename = "REGULAR")]
+    Regular,
+    #[serde(rename = "POST")]
+    Post,
+    #[serde(rename = "POSTPOST")]
+    PostPost,
+    #[serde(rename = "CLOSED")]
+    Closed,
+    #[serde(other)]
+    Unknown,
+}
+
+/// The current quote of a symbol.
+///
+/// Yahoo! finance leaves out the fields not available for the kind of instrument or for the
+/// current state of the market, e.g. the post-market price during the regular session.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteSnapshot {
+    pub symbol: String,
+    pub short_name: Option<String>,
+    pub long_name: Option<String>,
+    pub quote_type: Option<String>,
+    pub currency: Option<String>,
+    pub exchange: Option<String>,
+    pub market_state: Option<MarketState>,
+
+    pub regular_market_price: Option<f64>,
+    pub regular_market_change: Option<f64>,
+    pub regular_market_change_percent: Option<f64>,
+    pub regular_market_time: Option<i64>,
+    pub regular_market_open: Option<f64>,
+    pub regular_market_day_high: Option<f64>,
+    pub regular_market_day_low: Option<f64>,
+    pub regular_market_previous_close: Option<f64>,
+    pub regular_market_volume: Option<u64>,
+
+    pub pre_market_price: Option<f64>,
+    pub pre_market_change: Option<f64>,
+    pub pre_market_change_percent: Option<f64>,
+    pub pre_market_time: Option<i64>,
+
+    pub post_market_price: Option<f64>,
+    pub post_market_change: Option<f64>,
+    pub post_market_change_percent: Option<f64>,
+    pub post_market_time: Option<i64>,
+
+    pub bid: Option<f64>,
+    pub bid_size: Option<u64>,
+    pub ask: Option<f64>,
+    pub ask_size: Option<u64>,
+
+    pub market_cap: Option<u64>,
+    pub fifty_two_week_low: Option<f64>,
+    pub fifty_two_week_high: Option<f64>,
+}
+
+pub(crate) fn compose_url(base_url: &str, symbols: &[&str]) -> String {
+    format!(QUERY!(), url = base_url, symbols = symbols.join(","))
+}
+
+pub(crate) fn from_response(mut json: Value) -> Result<HashMap<String, QuoteSnapshot>, YahooError> {
+    if let Some(results) = json
+        .get_mut("quoteResponse")
+        .and_then(|v| v.get_mut("result"))
+    {
+        let snapshots: Vec<QuoteSnapshot> = serde_json::from_value(results.take())?;
+        return Ok(snapshots
+            .into_iter()
+            .map(|snapshot| (snapshot.symbol.clone(), snapshot))
+            .collect());
+    }
+    Err(YahooError::FetchFailed(
+        "quoteResponse.result not found in the response JSON".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"
+    {
+        "quoteResponse": {
+            "result": [
+                {
+                    "language": "en-US",
+                    "region": "US",
+                    "quoteType": "EQUITY",
+                    "typeDisp": "Equity",
+                    "currency": "USD",
+                    "exchange": "NMS",
+                    "shortName": "Apple Inc.",
+                    "longName": "Apple Inc.",
+                    "marketState": "POST",
+                    "regularMarketPrice": 229.87,
+                    "regularMarketChange": 1.35,
+                    "regularMarketChangePercent": 0.590721,
+                    "regularMarketTime": 1732309201,
+                    "regularMarketOpen": 228.06,
+                    "regularMarketDayHigh": 230.16,
+                    "regularMarketDayLow": 226.66,
+                    "regularMarketPreviousClose": 228.52,
+                    "regularMarketVolume": 38168252,
+                    "postMarketPrice": 229.95,
+                    "postMarketChange": 0.080002,
+                    "postMarketChangePercent": 0.0348031,
+                    "postMarketTime": 1732323597,
+                    "bid": 229.78,
+                    "bidSize": 3,
+                    "ask": 229.98,
+                    "askSize": 2,
+                    "marketCap": 3474700386304,
+                    "fiftyTwoWeekLow": 164.08,
+                    "fiftyTwoWeekHigh": 237.49,
+                    "symbol": "AAPL"
+                },
+                {
+                    "quoteType": "CURRENCY",
+                    "currency": "USD",
+                    "exchange": "CCY",
+                    "shortName": "EUR/USD",
+                    "marketState": "REGULAR",
+                    "regularMarketPrice": 1.0412,
+                    "regularMarketTime": 1732318200,
+                    "fiftyTwoWeekLow": 1.0335,
+                    "fiftyTwoWeekHigh": 1.1214,
+                    "symbol": "EURUSD=X"
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    #[test]
+    fn test_compose_url() {
+        assert_eq!(
+            compose_url(
+                "https://query1.finance.yahoo.com/v7/finance/quote",
+                &["AAPL", "EURUSD=X"]
+            ),
+            "https://query1.finance.yahoo.com/v7/finance/quote?symbols=AAPL,EURUSD=X"
+        );
+    }
+
+    #[test]
+    fn test_from_response() {
+        let snapshots = from_response(serde_json::from_str(RESPONSE).unwrap()).unwrap();
+        assert_eq!(snapshots.len(), 2);
+
+        let aapl = &snapshots["AAPL"];
+        assert_eq!(aapl.market_state, Some(MarketState::Post));
+        assert_eq!(aapl.regular_market_price, Some(229.87));
+        assert_eq!(aapl.post_market_price, Some(229.95));
+        assert_eq!(aapl.pre_market_price, None);
+        assert_eq!(aapl.bid, Some(229.78));
+        assert_eq!(aapl.ask_size, Some(2));
+        assert_eq!(aapl.market_cap, Some(3474700386304));
+        assert_eq!(aapl.fifty_two_week_high, Some(237.49));
+
+        let eur = &snapshots["EURUSD=X"];
+        assert_eq!(eur.quote_type.as_deref(), Some("CURRENCY"));
+        assert_eq!(eur.market_cap, None);
+        assert_eq!(eur.bid, None);
+
+        assert!(from_response(serde_json::json!({"finance": {"error": "Unauthorized"}})).is_err());
+    }
+}
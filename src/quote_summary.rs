@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 use serde_json::Value;
 
@@ -18,6 +20,21 @@ pub struct QuoteSummary {
     pub earnings_history: Option<EarningsHistory>,
     pub earnings_trend: Option<EarningsTrend>,
     pub price: Option<Price>,
+    pub calendar_events: Option<CalendarEvents>,
+    pub recommendation_trend: Option<RecommendationTrend>,
+    pub upgrade_downgrade_history: Option<UpgradeDowngradeHistory>,
+    pub institution_ownership: Option<Ownership>,
+    pub fund_ownership: Option<Ownership>,
+    pub major_holders_breakdown: Option<MajorHoldersBreakdown>,
+    pub insider_holders: Option<InsiderHolders>,
+    pub insider_transactions: Option<InsiderTransactions>,
+    pub net_share_purchase_activity: Option<NetSharePurchaseActivity>,
+    pub sec_filings: Option<SecFilings>,
+    pub fund_profile: Option<FundProfile>,
+    pub fund_performance: Option<FundPerformance>,
+    pub top_holdings: Option<TopHoldings>,
+    pub esg_scores: Option<EsgScores>,
+    pub index_trend: Option<IndexTrend>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -31,6 +48,21 @@ pub enum QuoteSummaryField {
     EarningsHistory,
     EarningsTrend,
     Price,
+    CalendarEvents,
+    RecommendationTrend,
+    UpgradeDowngradeHistory,
+    InstitutionOwnership,
+    FundOwnership,
+    MajorHoldersBreakdown,
+    InsiderHolders,
+    InsiderTransactions,
+    NetSharePurchaseActivity,
+    SecFilings,
+    FundProfile,
+    FundPerformance,
+    TopHoldings,
+    EsgScores,
+    IndexTrend,
 }
 
 impl QuoteSummaryField {
@@ -45,13 +77,68 @@ impl QuoteSummaryField {
             QuoteSummaryField::EarningsHistory => "earningsHistory",
             QuoteSummaryField::EarningsTrend => "earningsTrend",
             QuoteSummaryField::Price => "price",
+            QuoteSummaryField::CalendarEvents => "calendarEvents",
+            QuoteSummaryField::RecommendationTrend => "recommendationTrend",
+            QuoteSummaryField::UpgradeDowngradeHistory => "upgradeDowngradeHistory",
+            QuoteSummaryField::InstitutionOwnership => "institutionOwnership",
+            QuoteSummaryField::FundOwnership => "fundOwnership",
+            QuoteSummaryField::MajorHoldersBreakdown => "majorHoldersBreakdown",
+            QuoteSummaryField::InsiderHolders => "insiderHolders",
+            QuoteSummaryField::InsiderTransactions => "insiderTransactions",
+            QuoteSummaryField::NetSharePurchaseActivity => "netSharePurchaseActivity",
+            QuoteSummaryField::SecFilings => "secFilings",
+            QuoteSummaryField::FundProfile => "fundProfile",
+            QuoteSummaryField::FundPerformance => "fundPerformance",
+            QuoteSummaryField::TopHoldings => "topHoldings",
+            QuoteSummaryField::EsgScores => "esgScores",
+            QuoteSummaryField::IndexTrend => "indexTrend",
         }
     }
 }
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetProfile {
-    // eh, there's just too much of this...
+    pub address1: Option<String>,
+    pub address2: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip: Option<String>,
+    pub country: Option<String>,
+    pub phone: Option<String>,
+    pub website: Option<String>,
+    pub ir_website: Option<String>,
+    pub industry: Option<String>,
+    pub industry_key: Option<String>,
+    pub industry_disp: Option<String>,
+    pub sector: Option<String>,
+    pub sector_key: Option<String>,
+    pub sector_disp: Option<String>,
+    pub long_business_summary: Option<String>,
+    pub full_time_employees: Option<u64>,
+    pub company_officers: Option<Vec<CompanyOfficer>>,
+    /// Governance risk scores, from 1 (low) to 10 (high)
+    pub audit_risk: Option<u8>,
+    pub board_risk: Option<u8>,
+    pub compensation_risk: Option<u8>,
+    pub share_holder_rights_risk: Option<u8>,
+    pub overall_risk: Option<u8>,
+    pub governance_epoch_date: Option<i64>,
+    pub compensation_as_of_epoch_date: Option<i64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyOfficer {
+    pub name: Option<String>,
+    pub age: Option<u32>,
+    pub title: Option<String>,
+    pub year_born: Option<i32>,
+    pub fiscal_year: Option<i32>,
+    pub total_pay: Option<f64>,
+    pub exercised_value: Option<f64>,
+    pub unexercised_value: Option<f64>,
+    pub max_age: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -387,6 +474,396 @@ pub struct Price {
     pub open_interest: Option<f64>,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvents {
+    pub earnings: Option<CalendarEarnings>,
+    pub ex_dividend_date: Option<i64>,
+    pub dividend_date: Option<i64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEarnings {
+    pub earnings_date: Option<Vec<i64>>,
+    pub earnings_call_date: Option<Vec<i64>>,
+    pub is_earnings_date_estimate: Option<bool>,
+    pub earnings_average: Option<f64>,
+    pub earnings_low: Option<f64>,
+    pub earnings_high: Option<f64>,
+    pub revenue_average: Option<f64>,
+    pub revenue_low: Option<f64>,
+    pub revenue_high: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationTrend {
+    pub trend: Option<Vec<RecommendationTrendEntry>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationTrendEntry {
+    /// The period relative to the current month, e.g. "0m" or "-1m"
+    pub period: Option<String>,
+    pub strong_buy: Option<u32>,
+    pub buy: Option<u32>,
+    pub hold: Option<u32>,
+    pub sell: Option<u32>,
+    pub strong_sell: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeDowngradeHistory {
+    pub history: Option<Vec<UpgradeDowngrade>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeDowngrade {
+    pub epoch_grade_date: Option<i64>,
+    pub firm: Option<String>,
+    pub to_grade: Option<String>,
+    pub from_grade: Option<String>,
+    pub action: Option<String>,
+}
+
+/// The institutions or funds holding the most shares
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Ownership {
+    pub ownership_list: Option<Vec<OwnershipEntry>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipEntry {
+    pub report_date: Option<i64>,
+    pub organization: Option<String>,
+    pub pct_held: Option<f64>,
+    pub position: Option<f64>,
+    pub value: Option<f64>,
+    pub pct_change: Option<f64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MajorHoldersBreakdown {
+    pub insiders_percent_held: Option<f64>,
+    pub institutions_percent_held: Option<f64>,
+    pub institutions_float_percent_held: Option<f64>,
+    pub institutions_count: Option<u64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderHolders {
+    pub holders: Option<Vec<InsiderHolder>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderHolder {
+    pub name: Option<String>,
+    pub relation: Option<String>,
+    pub url: Option<String>,
+    pub transaction_description: Option<String>,
+    pub latest_trans_date: Option<i64>,
+    pub position_direct: Option<f64>,
+    pub position_direct_date: Option<i64>,
+    pub position_indirect: Option<f64>,
+    pub position_indirect_date: Option<i64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderTransactions {
+    pub transactions: Option<Vec<InsiderTransaction>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderTransaction {
+    pub filer_name: Option<String>,
+    pub filer_relation: Option<String>,
+    pub filer_url: Option<String>,
+    pub transaction_text: Option<String>,
+    pub money_text: Option<String>,
+    /// "D" for direct and "I" for indirect ownership
+    pub ownership: Option<String>,
+    pub start_date: Option<i64>,
+    pub shares: Option<f64>,
+    pub value: Option<f64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetSharePurchaseActivity {
+    pub period: Option<String>,
+    pub buy_info_count: Option<u64>,
+    pub buy_info_shares: Option<f64>,
+    pub buy_percent_insider_shares: Option<f64>,
+    pub sell_info_count: Option<u64>,
+    pub sell_info_shares: Option<f64>,
+    pub sell_percent_insider_shares: Option<f64>,
+    pub net_info_count: Option<u64>,
+    pub net_info_shares: Option<f64>,
+    pub net_percent_insider_shares: Option<f64>,
+    pub total_insider_shares: Option<f64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecFilings {
+    pub filings: Option<Vec<SecFiling>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecFiling {
+    pub date: Option<String>,
+    pub epoch_date: Option<i64>,
+    #[serde(rename = "type")]
+    pub filing_type: Option<String>,
+    pub title: Option<String>,
+    pub edgar_url: Option<String>,
+    pub exhibits: Option<Vec<SecFilingExhibit>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecFilingExhibit {
+    #[serde(rename = "type")]
+    pub exhibit_type: Option<String>,
+    pub url: Option<String>,
+    pub download_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundProfile {
+    pub category_name: Option<String>,
+    pub family: Option<String>,
+    pub legal_type: Option<String>,
+    pub style_box_url: Option<String>,
+    pub fees_expenses_investment: Option<FundFees>,
+    /// The fees of the fund category, for comparison
+    pub fees_expenses_investment_cat: Option<FundFees>,
+    pub management_info: Option<FundManagementInfo>,
+    pub initial_investment: Option<f64>,
+    pub subseq_investment: Option<f64>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundFees {
+    pub annual_report_expense_ratio: Option<f64>,
+    pub annual_holdings_turnover: Option<f64>,
+    pub total_net_assets: Option<f64>,
+    pub gross_exp_ratio: Option<f64>,
+    pub net_exp_ratio: Option<f64>,
+    #[serde(rename = "twelveBOne")]
+    pub twelve_b_one: Option<f64>,
+    pub front_end_sales_load: Option<f64>,
+    pub deferred_sales_load: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundManagementInfo {
+    pub manager_name: Option<String>,
+    pub manager_bio: Option<String>,
+    pub starting_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundPerformance {
+    pub performance_overview: Option<FundPerformanceOverview>,
+    /// The trailing total returns based on the market price
+    pub trailing_returns: Option<TrailingReturns>,
+    /// The trailing total returns based on the net asset value
+    pub trailing_returns_nav: Option<TrailingReturns>,
+    /// The trailing total returns of the fund category, for comparison
+    pub trailing_returns_cat: Option<TrailingReturns>,
+    pub annual_total_returns: Option<AnnualTotalReturns>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundPerformanceOverview {
+    pub as_of_date: Option<i64>,
+    pub ytd_return_pct: Option<f64>,
+    pub five_yr_avg_return_pct: Option<f64>,
+    pub morning_star_return_rating: Option<u8>,
+    pub num_years_up: Option<u32>,
+    pub num_years_down: Option<u32>,
+    pub best_one_yr_total_return: Option<f64>,
+    pub worst_one_yr_total_return: Option<f64>,
+    pub best_three_yr_total_return: Option<f64>,
+    pub worst_three_yr_total_return: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrailingReturns {
+    pub as_of_date: Option<i64>,
+    pub ytd: Option<f64>,
+    pub one_month: Option<f64>,
+    pub three_month: Option<f64>,
+    pub one_year: Option<f64>,
+    pub three_year: Option<f64>,
+    pub five_year: Option<f64>,
+    pub ten_year: Option<f64>,
+    pub last_bull_mkt: Option<f64>,
+    pub last_bear_mkt: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnualTotalReturns {
+    pub returns: Option<Vec<AnnualReturn>>,
+    /// The annual returns of the fund category, for comparison
+    pub returns_cat: Option<Vec<AnnualReturn>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnualReturn {
+    pub year: Option<String>,
+    pub annual_value: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TopHoldings {
+    pub cash_position: Option<f64>,
+    pub stock_position: Option<f64>,
+    pub bond_position: Option<f64>,
+    pub preferred_position: Option<f64>,
+    pub convertible_position: Option<f64>,
+    pub other_position: Option<f64>,
+    pub holdings: Option<Vec<Holding>>,
+    pub equity_holdings: Option<EquityHoldings>,
+    pub bond_holdings: Option<BondHoldings>,
+    /// The weights of the credit ratings, e.g. `[{"aaa": 0.52}, {"aa": 0.11}]`
+    pub bond_ratings: Option<Vec<HashMap<String, f64>>>,
+    /// The weights of the sectors, e.g. `[{"technology": 0.31}, {"healthcare": 0.12}]`
+    pub sector_weightings: Option<Vec<HashMap<String, f64>>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Holding {
+    pub symbol: Option<String>,
+    pub holding_name: Option<String>,
+    pub holding_percent: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityHoldings {
+    pub price_to_earnings: Option<f64>,
+    pub price_to_book: Option<f64>,
+    pub price_to_sales: Option<f64>,
+    pub price_to_cashflow: Option<f64>,
+    pub median_market_cap: Option<f64>,
+    pub three_year_earnings_growth: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BondHoldings {
+    pub duration: Option<f64>,
+    pub maturity: Option<f64>,
+    pub credit_quality: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EsgScores {
+    pub total_esg: Option<f64>,
+    pub environment_score: Option<f64>,
+    pub social_score: Option<f64>,
+    pub governance_score: Option<f64>,
+    pub esg_performance: Option<String>,
+    pub peer_group: Option<String>,
+    pub peer_count: Option<u32>,
+    /// The percentile of the total score among all the rated companies, lower is better
+    pub percentile: Option<f64>,
+    pub environment_percentile: Option<f64>,
+    pub social_percentile: Option<f64>,
+    pub governance_percentile: Option<f64>,
+    pub peer_esg_score_performance: Option<PeerPerformance>,
+    pub peer_environment_performance: Option<PeerPerformance>,
+    pub peer_social_performance: Option<PeerPerformance>,
+    pub peer_governance_performance: Option<PeerPerformance>,
+    pub peer_highest_controversy_performance: Option<PeerPerformance>,
+    pub rating_year: Option<i32>,
+    pub rating_month: Option<u32>,
+    pub highest_controversy: Option<f64>,
+    pub related_controversy: Option<Vec<String>>,
+    pub adult: Option<bool>,
+    pub alcoholic: Option<bool>,
+    pub animal_testing: Option<bool>,
+    pub catholic: Option<bool>,
+    pub controversial_weapons: Option<bool>,
+    pub small_arms: Option<bool>,
+    pub fur_leather: Option<bool>,
+    pub gambling: Option<bool>,
+    pub gmo: Option<bool>,
+    pub military_contract: Option<bool>,
+    pub nuclear: Option<bool>,
+    pub pesticides: Option<bool>,
+    pub palm_oil: Option<bool>,
+    pub coal: Option<bool>,
+    pub tobacco: Option<bool>,
+    pub max_age: Option<usize>,
+}
+
+/// The range of a score among the peers
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct PeerPerformance {
+    pub min: Option<f64>,
+    pub avg: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexTrend {
+    pub symbol: Option<String>,
+    pub pe_ratio: Option<f64>,
+    pub peg_ratio: Option<f64>,
+    pub estimates: Option<Vec<IndexTrendEstimate>>,
+    pub max_age: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexTrendEstimate {
+    pub period: Option<String>,
+    pub growth: Option<f64>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Formatted<T> {
     pub fmt: Option<String>,
@@ -395,11 +872,11 @@ pub struct Formatted<T> {
 
 macro_rules! QUERY {
     () => {
-        "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{symbol}?modules={modules}&corsDomain=finance.yahoo.com&formatted=false&symbol={symbol}"
+        "{url}/{symbol}?modules={modules}&corsDomain=finance.yahoo.com&formatted=false&symbol={symbol}"
     };
 }
 
-pub(crate) fn compose_url(symbol: &str, fields: &[QuoteSummaryField]) -> String {
+pub(crate) fn compose_url(base_url: &str, symbol: &str, fields: &[QuoteSummaryField]) -> String {
     let mut modules = String::new();
     if fields.len() > 0 {
         modules.push_str(fields[0].as_str());
@@ -409,7 +886,7 @@ pub(crate) fn compose_url(symbol: &str, fields: &[QuoteSummaryField]) -> String
         }
     }
 
-    format!(QUERY!(), symbol = symbol, modules = modules)
+    format!(QUERY!(), url = base_url, symbol = symbol, modules = modules)
 }
 
 pub(crate) fn from_response(mut json: Value) -> Result<QuoteSummary, YahooError> {
@@ -2110,4 +2587,593 @@ mod test {
 
         assert!(data.earnings_history.is_some());
     }
+
+    /// Parse the modules as the only result of a quoteSummary response.
+    fn summary(modules: &str) -> QuoteSummary {
+        let raw = format!(
+            r#"{{"quoteSummary": {{"result": [{}], "error": null}}}}"#,
+            modules
+        );
+        from_response(serde_json::from_str(&raw).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_asset_profile() {
+        let data = summary(
+            r#"{
+                "assetProfile": {
+                    "address1": "One Apple Park Way",
+                    "city": "Cupertino",
+                    "state": "CA",
+                    "zip": "95014",
+                    "country": "United States",
+                    "phone": "(408) 996-1010",
+                    "website": "https://www.apple.com",
+                    "industry": "Consumer Electronics",
+                    "industryKey": "consumer-electronics",
+                    "industryDisp": "Consumer Electronics",
+                    "sector": "Technology",
+                    "sectorKey": "technology",
+                    "sectorDisp": "Technology",
+                    "longBusinessSummary": "Apple Inc. designs, manufactures, and markets smartphones.",
+                    "fullTimeEmployees": 164000,
+                    "companyOfficers": [
+                        {
+                            "maxAge": 1,
+                            "name": "Mr. Timothy D. Cook",
+                            "age": 62,
+                            "title": "CEO & Director",
+                            "yearBorn": 1961,
+                            "fiscalYear": 2023,
+                            "totalPay": 16239562,
+                            "exercisedValue": 0,
+                            "unexercisedValue": 0
+                        },
+                        {
+                            "maxAge": 1,
+                            "name": "Ms. Kristin Huguet Quayle",
+                            "title": "Vice President of Worldwide Communications",
+                            "fiscalYear": 2023,
+                            "exercisedValue": 0,
+                            "unexercisedValue": 0
+                        }
+                    ],
+                    "auditRisk": 6,
+                    "boardRisk": 1,
+                    "compensationRisk": 2,
+                    "shareHolderRightsRisk": 1,
+                    "overallRisk": 1,
+                    "governanceEpochDate": 1727740800,
+                    "compensationAsOfEpochDate": 1703980800,
+                    "irWebsite": "http://investor.apple.com/",
+                    "maxAge": 86400
+                }
+            }"#,
+        );
+        let profile = data.asset_profile.unwrap();
+        assert_eq!(profile.sector.as_deref(), Some("Technology"));
+        assert_eq!(profile.industry.as_deref(), Some("Consumer Electronics"));
+        assert_eq!(profile.full_time_employees, Some(164000));
+        assert_eq!(profile.audit_risk, Some(6));
+        assert_eq!(profile.overall_risk, Some(1));
+        assert_eq!(profile.governance_epoch_date, Some(1727740800));
+
+        let officers = profile.company_officers.unwrap();
+        assert_eq!(officers.len(), 2);
+        assert_eq!(officers[0].name.as_deref(), Some("Mr. Timothy D. Cook"));
+        assert_eq!(officers[0].total_pay, Some(16239562.0));
+        assert_eq!(officers[1].age, None);
+        assert_eq!(officers[1].total_pay, None);
+    }
+
+    #[test]
+    fn test_calendar_events() {
+        let data = summary(
+            r#"{
+                "calendarEvents": {
+                    "maxAge": 1,
+                    "earnings": {
+                        "earningsDate": [1730332800, 1730419200],
+                        "earningsCallDate": [1730406600],
+                        "isEarningsDateEstimate": false,
+                        "earningsAverage": 1.6,
+                        "earningsLow": 1.55,
+                        "earningsHigh": 1.63,
+                        "revenueAverage": 94504000000,
+                        "revenueLow": 94010000000,
+                        "revenueHigh": 95700000000
+                    },
+                    "exDividendDate": 1731024000,
+                    "dividendDate": 1731542400
+                }
+            }"#,
+        );
+        let events = data.calendar_events.unwrap();
+        assert_eq!(events.ex_dividend_date, Some(1731024000));
+        assert_eq!(events.dividend_date, Some(1731542400));
+
+        let earnings = events.earnings.unwrap();
+        assert_eq!(earnings.earnings_date, Some(vec![1730332800, 1730419200]));
+        assert_eq!(earnings.is_earnings_date_estimate, Some(false));
+        assert_eq!(earnings.earnings_average, Some(1.6));
+        assert_eq!(earnings.revenue_high, Some(95700000000.0));
+    }
+
+    #[test]
+    fn test_recommendation_trend() {
+        let data = summary(
+            r#"{
+                "recommendationTrend": {
+                    "trend": [
+                        {"period": "0m", "strongBuy": 8, "buy": 24, "hold": 12, "sell": 1, "strongSell": 2},
+                        {"period": "-1m", "strongBuy": 8, "buy": 23, "hold": 13, "sell": 1, "strongSell": 2}
+                    ],
+                    "maxAge": 86400
+                }
+            }"#,
+        );
+        let trend = data.recommendation_trend.unwrap().trend.unwrap();
+        assert_eq!(trend.len(), 2);
+        assert_eq!(trend[0].period.as_deref(), Some("0m"));
+        assert_eq!(trend[0].strong_buy, Some(8));
+        assert_eq!(trend[1].hold, Some(13));
+        assert_eq!(trend[1].strong_sell, Some(2));
+    }
+
+    #[test]
+    fn test_upgrade_downgrade_history() {
+        let data = summary(
+            r#"{
+                "upgradeDowngradeHistory": {
+                    "history": [
+                        {"epochGradeDate": 1730419200, "firm": "Maxim Group", "toGrade": "Buy", "fromGrade": "Hold", "action": "up"},
+                        {"epochGradeDate": 1730332800, "firm": "Wedbush", "toGrade": "Outperform", "fromGrade": "", "action": "main"}
+                    ],
+                    "maxAge": 86400
+                }
+            }"#,
+        );
+        let history = data.upgrade_downgrade_history.unwrap().history.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].epoch_grade_date, Some(1730419200));
+        assert_eq!(history[0].firm.as_deref(), Some("Maxim Group"));
+        assert_eq!(history[0].action.as_deref(), Some("up"));
+        assert_eq!(history[1].from_grade.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_ownership() {
+        let data = summary(
+            r#"{
+                "institutionOwnership": {
+                    "maxAge": 1,
+                    "ownershipList": [
+                        {
+                            "maxAge": 1,
+                            "reportDate": 1719705600,
+                            "organization": "Vanguard Group Inc",
+                            "pctHeld": 0.0876,
+                            "position": 1331903760,
+                            "value": 315489713071,
+                            "pctChange": -0.0045
+                        }
+                    ]
+                },
+                "fundOwnership": {
+                    "maxAge": 1,
+                    "ownershipList": [
+                        {
+                            "maxAge": 1,
+                            "reportDate": 1719705600,
+                            "organization": "Vanguard Total Stock Market Index Fund",
+                            "pctHeld": 0.0296,
+                            "position": 450023542,
+                            "value": 106596576533,
+                            "pctChange": 0.0057
+                        },
+                        {
+                            "maxAge": 1,
+                            "reportDate": 1719705600,
+                            "organization": "SPDR S&P 500 ETF Trust",
+                            "pctHeld": 0.0107,
+                            "position": 163190516,
+                            "value": 38654938568
+                        }
+                    ]
+                }
+            }"#,
+        );
+        let institutions = data.institution_ownership.unwrap().ownership_list.unwrap();
+        assert_eq!(institutions.len(), 1);
+        assert_eq!(
+            institutions[0].organization.as_deref(),
+            Some("Vanguard Group Inc")
+        );
+        assert_eq!(institutions[0].pct_held, Some(0.0876));
+        assert_eq!(institutions[0].position, Some(1331903760.0));
+        assert_eq!(institutions[0].report_date, Some(1719705600));
+
+        let funds = data.fund_ownership.unwrap().ownership_list.unwrap();
+        assert_eq!(funds.len(), 2);
+        assert_eq!(funds[0].pct_change, Some(0.0057));
+        assert_eq!(funds[1].pct_change, None);
+    }
+
+    #[test]
+    fn test_major_holders_breakdown() {
+        let data = summary(
+            r#"{
+                "majorHoldersBreakdown": {
+                    "maxAge": 1,
+                    "insidersPercentHeld": 0.02703,
+                    "institutionsPercentHeld": 0.61514,
+                    "institutionsFloatPercentHeld": 0.6322,
+                    "institutionsCount": 6659
+                }
+            }"#,
+        );
+        let breakdown = data.major_holders_breakdown.unwrap();
+        assert_eq!(breakdown.insiders_percent_held, Some(0.02703));
+        assert_eq!(breakdown.institutions_percent_held, Some(0.61514));
+        assert_eq!(breakdown.institutions_float_percent_held, Some(0.6322));
+        assert_eq!(breakdown.institutions_count, Some(6659));
+    }
+
+    #[test]
+    fn test_insider_holders() {
+        let data = summary(
+            r#"{
+                "insiderHolders": {
+                    "holders": [
+                        {
+                            "maxAge": 1,
+                            "name": "COOK TIMOTHY D",
+                            "relation": "Chief Executive Officer",
+                            "url": "",
+                            "transactionDescription": "Sale",
+                            "latestTransDate": 1712102400,
+                            "positionDirect": 3280180,
+                            "positionDirectDate": 1712102400
+                        },
+                        {
+                            "maxAge": 1,
+                            "name": "LEVINSON ARTHUR D",
+                            "relation": "Director",
+                            "url": "",
+                            "transactionDescription": "Sale",
+                            "latestTransDate": 1722988800,
+                            "positionDirect": 4215702,
+                            "positionDirectDate": 1722988800,
+                            "positionIndirect": 1000000,
+                            "positionIndirectDate": 1722988800
+                        }
+                    ],
+                    "maxAge": 1
+                }
+            }"#,
+        );
+        let holders = data.insider_holders.unwrap().holders.unwrap();
+        assert_eq!(holders.len(), 2);
+        assert_eq!(holders[0].name.as_deref(), Some("COOK TIMOTHY D"));
+        assert_eq!(holders[0].position_direct, Some(3280180.0));
+        assert_eq!(holders[0].position_indirect, None);
+        assert_eq!(holders[1].latest_trans_date, Some(1722988800));
+        assert_eq!(holders[1].position_indirect, Some(1000000.0));
+    }
+
+    #[test]
+    fn test_insider_transactions() {
+        let data = summary(
+            r#"{
+                "insiderTransactions": {
+                    "transactions": [
+                        {
+                            "maxAge": 1,
+                            "shares": 4130,
+                            "value": 942776,
+                            "filerUrl": "",
+                            "transactionText": "Sale at price 228.28 per share.",
+                            "filerName": "ADAMS KATHERINE L",
+                            "filerRelation": "General Counsel",
+                            "moneyText": "",
+                            "startDate": 1730419200,
+                            "ownership": "D"
+                        },
+                        {
+                            "maxAge": 1,
+                            "shares": 8570,
+                            "filerUrl": "",
+                            "transactionText": "",
+                            "filerName": "ADAMS KATHERINE L",
+                            "filerRelation": "General Counsel",
+                            "moneyText": "",
+                            "startDate": 1730332800,
+                            "ownership": "I"
+                        }
+                    ],
+                    "maxAge": 1
+                }
+            }"#,
+        );
+        let transactions = data.insider_transactions.unwrap().transactions.unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].shares, Some(4130.0));
+        assert_eq!(transactions[0].value, Some(942776.0));
+        assert_eq!(
+            transactions[0].filer_relation.as_deref(),
+            Some("General Counsel")
+        );
+        assert_eq!(transactions[0].start_date, Some(1730419200));
+        assert_eq!(transactions[1].value, None);
+        assert_eq!(transactions[1].ownership.as_deref(), Some("I"));
+    }
+
+    #[test]
+    fn test_net_share_purchase_activity() {
+        let data = summary(
+            r#"{
+                "netSharePurchaseActivity": {
+                    "maxAge": 1,
+                    "period": "6m",
+                    "buyInfoCount": 8,
+                    "buyInfoShares": 520454,
+                    "buyPercentInsiderShares": 0.007,
+                    "sellInfoCount": 20,
+                    "sellInfoShares": 2086283,
+                    "sellPercentInsiderShares": 0.028,
+                    "netInfoCount": 28,
+                    "netInfoShares": -1565829,
+                    "netPercentInsiderShares": -0.021,
+                    "totalInsiderShares": 72618426
+                }
+            }"#,
+        );
+        let activity = data.net_share_purchase_activity.unwrap();
+        assert_eq!(activity.period.as_deref(), Some("6m"));
+        assert_eq!(activity.buy_info_count, Some(8));
+        assert_eq!(activity.sell_info_shares, Some(2086283.0));
+        assert_eq!(activity.net_info_shares, Some(-1565829.0));
+        assert_eq!(activity.total_insider_shares, Some(72618426.0));
+    }
+
+    #[test]
+    fn test_sec_filings() {
+        let data = summary(
+            r#"{
+                "secFilings": {
+                    "filings": [
+                        {
+                            "date": "2024-11-01",
+                            "epochDate": 1730419200,
+                            "type": "10-K",
+                            "title": "Annual Report",
+                            "edgarUrl": "https://finance.yahoo.com/sec-filing/AAPL/0000320193-24-000123_320193",
+                            "exhibits": [
+                                {
+                                    "type": "10-K",
+                                    "url": "https://cdn.yahoofinance.com/prod/sec-filings/0000320193/000032019324000123/aapl-20240928.htm",
+                                    "downloadUrl": "https://cdn.yahoofinance.com/prod/sec-filings/0000320193/000032019324000123/aapl-20240928.pdf"
+                                },
+                                {
+                                    "type": "EX-21.1",
+                                    "url": "https://cdn.yahoofinance.com/prod/sec-filings/0000320193/000032019324000123/a10-kexhibit2112024.htm"
+                                }
+                            ],
+                            "maxAge": 1
+                        },
+                        {
+                            "date": "2024-10-31",
+                            "epochDate": 1730332800,
+                            "type": "8-K",
+                            "title": "Corporate Changes & Voting Matters",
+                            "edgarUrl": "https://finance.yahoo.com/sec-filing/AAPL/0000320193-24-000120_320193",
+                            "maxAge": 1
+                        }
+                    ],
+                    "maxAge": 86400
+                }
+            }"#,
+        );
+        let filings = data.sec_filings.unwrap().filings.unwrap();
+        assert_eq!(filings.len(), 2);
+        assert_eq!(filings[0].filing_type.as_deref(), Some("10-K"));
+        assert_eq!(filings[0].epoch_date, Some(1730419200));
+
+        let exhibits = filings[0].exhibits.as_ref().unwrap();
+        assert_eq!(exhibits.len(), 2);
+        assert_eq!(exhibits[1].exhibit_type.as_deref(), Some("EX-21.1"));
+        assert!(exhibits[0].download_url.is_some());
+        assert!(exhibits[1].download_url.is_none());
+
+        assert_eq!(filings[1].date.as_deref(), Some("2024-10-31"));
+        assert!(filings[1].exhibits.is_none());
+    }
+
+    #[test]
+    fn test_fund_profile() {
+        let data = summary(
+            r#"{
+                "fundProfile": {
+                    "maxAge": 1,
+                    "styleBoxUrl": "https://s.yimg.com/lq/i/fi/3_0stylelargeeq2.gif",
+                    "family": "Vanguard",
+                    "categoryName": "Large Blend",
+                    "legalType": "Exchange Traded Fund",
+                    "managementInfo": {
+                        "managerName": "Michelle Louie",
+                        "managerBio": "Michelle Louie is a portfolio manager at Vanguard.",
+                        "startingDate": "2023-02-23"
+                    },
+                    "feesExpensesInvestment": {
+                        "annualReportExpenseRatio": 0.0003,
+                        "annualHoldingsTurnover": 0.02,
+                        "totalNetAssets": 1418550000000,
+                        "grossExpRatio": 0.0003,
+                        "netExpRatio": 0.0003
+                    },
+                    "feesExpensesInvestmentCat": {
+                        "annualReportExpenseRatio": 0.0084,
+                        "annualHoldingsTurnover": 0.45,
+                        "totalNetAssets": 9430000000,
+                        "twelveBOne": 0.0025
+                    },
+                    "initInvestment": 3000,
+                    "initialInvestment": 3000,
+                    "subseqInvestment": 1
+                }
+            }"#,
+        );
+        let profile = data.fund_profile.unwrap();
+        assert_eq!(profile.family.as_deref(), Some("Vanguard"));
+        assert_eq!(profile.category_name.as_deref(), Some("Large Blend"));
+        assert_eq!(profile.legal_type.as_deref(), Some("Exchange Traded Fund"));
+        assert_eq!(profile.initial_investment, Some(3000.0));
+
+        let fees = profile.fees_expenses_investment.unwrap();
+        assert_eq!(fees.annual_report_expense_ratio, Some(0.0003));
+        assert_eq!(fees.net_exp_ratio, Some(0.0003));
+        assert_eq!(fees.twelve_b_one, None);
+        assert_eq!(
+            profile.fees_expenses_investment_cat.unwrap().twelve_b_one,
+            Some(0.0025)
+        );
+        assert_eq!(
+            profile.management_info.unwrap().manager_name.as_deref(),
+            Some("Michelle Louie")
+        );
+    }
+
+    #[test]
+    fn test_top_holdings() {
+        let data = summary(
+            r#"{
+                "topHoldings": {
+                    "maxAge": 1,
+                    "stockPosition": 0.9962,
+                    "bondPosition": 0,
+                    "cashPosition": 0.0038,
+                    "otherPosition": 0,
+                    "preferredPosition": 0,
+                    "convertiblePosition": 0,
+                    "holdings": [
+                        {"symbol": "AAPL", "holdingName": "Apple Inc", "holdingPercent": 0.0601},
+                        {"symbol": "NVDA", "holdingName": "NVIDIA Corp", "holdingPercent": 0.0587}
+                    ],
+                    "equityHoldings": {
+                        "priceToEarnings": 0.0417,
+                        "priceToBook": 0.2271,
+                        "priceToSales": 0.3386,
+                        "priceToCashflow": 0.0624,
+                        "medianMarketCap": 173000,
+                        "threeYearEarningsGrowth": 0.1134
+                    },
+                    "bondHoldings": {},
+                    "bondRatings": [{"bb": 0}, {"aa": 0}, {"aaa": 0}],
+                    "sectorWeightings": [
+                        {"realestate": 0.0287},
+                        {"technology": 0.3172},
+                        {"healthcare": 0.1149}
+                    ]
+                }
+            }"#,
+        );
+        let top = data.top_holdings.unwrap();
+        assert_eq!(top.stock_position, Some(0.9962));
+        assert_eq!(top.bond_position, Some(0.0));
+
+        let holdings = top.holdings.unwrap();
+        assert_eq!(holdings.len(), 2);
+        assert_eq!(holdings[0].symbol.as_deref(), Some("AAPL"));
+        assert_eq!(holdings[1].holding_percent, Some(0.0587));
+
+        assert_eq!(top.equity_holdings.unwrap().price_to_book, Some(0.2271));
+        assert!(top.bond_holdings.unwrap().duration.is_none());
+        assert_eq!(top.bond_ratings.unwrap()[2]["aaa"], 0.0);
+        let sectors = top.sector_weightings.unwrap();
+        assert_eq!(sectors.len(), 3);
+        assert_eq!(sectors[1]["technology"], 0.3172);
+    }
+
+    #[test]
+    fn test_esg_scores() {
+        let data = summary(
+            r#"{
+                "esgScores": {
+                    "maxAge": 86400,
+                    "totalEsg": 18.48,
+                    "environmentScore": 0.6,
+                    "socialScore": 7.71,
+                    "governanceScore": 10.17,
+                    "ratingYear": 2024,
+                    "ratingMonth": 10,
+                    "highestControversy": 3,
+                    "peerCount": 57,
+                    "esgPerformance": "UNDER_PERF",
+                    "peerGroup": "Technology Hardware",
+                    "relatedControversy": ["Customer Incidents", "Business Ethics Incidents"],
+                    "percentile": 14.71,
+                    "environmentPercentile": null,
+                    "socialPercentile": null,
+                    "governancePercentile": null,
+                    "peerEsgScorePerformance": {"min": 12.26, "avg": 19.08, "max": 32.73},
+                    "peerGovernancePerformance": {"min": 2.92, "avg": 6.28, "max": 12.35},
+                    "adult": false,
+                    "alcoholic": false,
+                    "animalTesting": false,
+                    "catholic": false,
+                    "controversialWeapons": false,
+                    "smallArms": false,
+                    "furLeather": false,
+                    "gambling": false,
+                    "gmo": false,
+                    "militaryContract": false,
+                    "nuclear": false,
+                    "pesticides": false,
+                    "palmOil": false,
+                    "coal": false,
+                    "tobacco": false
+                }
+            }"#,
+        );
+        let esg = data.esg_scores.unwrap();
+        assert_eq!(esg.total_esg, Some(18.48));
+        assert_eq!(esg.governance_score, Some(10.17));
+        assert_eq!(esg.esg_performance.as_deref(), Some("UNDER_PERF"));
+        assert_eq!(esg.rating_year, Some(2024));
+        assert_eq!(esg.rating_month, Some(10));
+        assert_eq!(esg.peer_count, Some(57));
+        assert_eq!(esg.related_controversy.unwrap().len(), 2);
+        assert_eq!(esg.controversial_weapons, Some(false));
+        assert_eq!(esg.governance_percentile, None);
+        assert_eq!(esg.peer_esg_score_performance.unwrap().avg, Some(19.08));
+        assert_eq!(esg.peer_governance_performance.unwrap().max, Some(12.35));
+        assert!(esg.peer_social_performance.is_none());
+    }
+
+    #[test]
+    fn test_index_trend() {
+        let data = summary(
+            r#"{
+                "indexTrend": {
+                    "maxAge": 1,
+                    "symbol": "SP5",
+                    "peRatio": 23.5,
+                    "pegRatio": 2.35,
+                    "estimates": [
+                        {"period": "0q", "growth": 0.052},
+                        {"period": "+1q", "growth": 0.121},
+                        {"period": "+5y"}
+                    ]
+                }
+            }"#,
+        );
+        let trend = data.index_trend.unwrap();
+        assert_eq!(trend.symbol.as_deref(), Some("SP5"));
+        assert_eq!(trend.pe_ratio, Some(23.5));
+        let estimates = trend.estimates.unwrap();
+        assert_eq!(estimates.len(), 3);
+        assert_eq!(estimates[1].period.as_deref(), Some("+1q"));
+        assert_eq!(estimates[1].growth, Some(0.121));
+        assert_eq!(estimates[2].growth, None);
+    }
 }
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
 
 use serde::{
     de::{self, Deserializer, MapAccess, SeqAccess, Visitor},
@@ -41,6 +44,112 @@ impl YResponse {
         Ok(serde_json::from_value(json)?)
     }
 
+    /// Merge the responses for consecutive periods of the same ticker into one response.
+    ///
+    /// The quotes are sorted by their timestamps and quotes with the same timestamp are taken
+    /// from the later response. The events and the trading periods of all the responses are
+    /// combined, the remaining metadata is taken from the last response.
+    pub fn merge<I>(responses: I) -> Result<YResponse, YahooError>
+    where
+        I: IntoIterator<Item = YResponse>,
+    {
+        let mut meta: Option<YMetaData> = None;
+        let mut rows = BTreeMap::new();
+        let mut with_adjclose = false;
+        let mut events: Option<EventsBlock> = None;
+
+        for response in responses {
+            match response.check_consistency() {
+                Ok(()) => {}
+                Err(YahooError::EmptyDataSet) => continue,
+                Err(e) => return Err(e),
+            }
+            let stock = match response.chart.result.into_iter().next() {
+                Some(stock) => stock,
+                None => continue,
+            };
+
+            let trading_periods = match meta.take() {
+                Some(previous) => previous
+                    .trading_periods
+                    .merge(stock.meta.trading_periods.clone()),
+                None => stock.meta.trading_periods.clone(),
+            };
+            meta = Some(YMetaData {
+                trading_periods,
+                ..stock.meta
+            });
+
+            let quote = &stock.indicators.quote[0];
+            let adjclose = stock.indicators.adjclose.as_ref().map(|a| &a[0].adjclose);
+            with_adjclose |= adjclose.is_some();
+            for (i, timestamp) in stock.timestamp.iter().enumerate() {
+                rows.insert(
+                    *timestamp,
+                    (
+                        quote.volume[i],
+                        quote.high[i],
+                        quote.close[i],
+                        quote.low[i],
+                        quote.open[i],
+                        adjclose.and_then(|a| a[i]),
+                    ),
+                );
+            }
+
+            if let Some(new) = stock.events {
+                let merged = events.get_or_insert(EventsBlock {
+                    splits: None,
+                    dividends: None,
+                    capital_gains: None,
+                });
+                merge_events(&mut merged.splits, new.splits);
+                merge_events(&mut merged.dividends, new.dividends);
+                merge_events(&mut merged.capital_gains, new.capital_gains);
+            }
+        }
+
+        let meta = meta.ok_or(YahooError::EmptyDataSet)?;
+        let n = rows.len();
+        let mut timestamp = Vec::with_capacity(n);
+        let mut quote = QuoteList {
+            volume: Vec::with_capacity(n),
+            high: Vec::with_capacity(n),
+            close: Vec::with_capacity(n),
+            low: Vec::with_capacity(n),
+            open: Vec::with_capacity(n),
+        };
+        let mut adjclose = Vec::with_capacity(n);
+        for (ts, (volume, high, close, low, open, adj)) in rows {
+            timestamp.push(ts);
+            quote.volume.push(volume);
+            quote.high.push(high);
+            quote.close.push(close);
+            quote.low.push(low);
+            quote.open.push(open);
+            adjclose.push(adj);
+        }
+
+        Ok(YResponse {
+            chart: YChart {
+                result: vec![YQuoteBlock {
+                    meta,
+                    timestamp,
+                    events,
+                    indicators: QuoteBlock {
+                        quote: vec![quote],
+                        adjclose: if with_adjclose {
+                            Some(vec![AdjClose { adjclose }])
+                        } else {
+                            None
+                        },
+                    },
+                }],
+                error: None,
+            },
+        })
+    }
+
     /// Return the latest valid quote
     pub fn last_quote(&self) -> Result<Quote, YahooError> {
         self.check_consistency()?;
@@ -48,8 +157,9 @@ impl YResponse {
         let n = stock.timestamp.len();
         for i in (0..n).rev() {
             let quote = stock.indicators.get_ith_quote(stock.timestamp[i], i);
-            if quote.is_ok() {
-                return quote;
+            if let Ok(mut q) = quote {
+                q.session = stock.meta.trading_periods.session(q.timestamp);
+                return Ok(q);
             }
         }
         Err(YahooError::EmptyDataSet)
@@ -63,13 +173,49 @@ impl YResponse {
         for i in 0..n {
             let timestamp = stock.timestamp[i];
             let quote = stock.indicators.get_ith_quote(timestamp, i);
-            if let Ok(q) = quote {
+            if let Ok(mut q) = quote {
+                q.session = stock.meta.trading_periods.session(timestamp);
                 quotes.push(q);
             }
         }
         Ok(quotes)
     }
 
+    /// Return all the quotes with the missing values left empty, together with the timestamps
+    /// of the quotes skipped by [`YResponse::quotes`] and of the quotes it fills with zeros.
+    pub fn partial_quotes(&self) -> Result<QuoteReport, YahooError> {
+        self.check_consistency()?;
+        let stock: &YQuoteBlock = &self.chart.result[0];
+        let mut report = QuoteReport::default();
+        for (i, timestamp) in stock.timestamp.iter().enumerate() {
+            let mut quote = stock.indicators.get_ith_partial_quote(*timestamp, i);
+            quote.session = stock.meta.trading_periods.session(*timestamp);
+            if quote.close.is_none() {
+                report.dropped.push(*timestamp);
+            } else if !quote.is_complete(stock.indicators.adjclose.is_some()) {
+                report.partial.push(*timestamp);
+            }
+            report.quotes.push(quote);
+        }
+        Ok(report)
+    }
+
+    /// Return the quotes traded in the given session
+    ///
+    /// Quotes not covered by the trading periods of the response are skipped.
+    pub fn session_quotes(&self, session: Session) -> Result<Vec<Quote>, YahooError> {
+        Ok(self
+            .quotes()?
+            .into_iter()
+            .filter(|q| q.session == Some(session))
+            .collect())
+    }
+
+    /// Return the quotes traded in the regular session, without the pre- and post-market quotes
+    pub fn regular_quotes(&self) -> Result<Vec<Quote>, YahooError> {
+        self.session_quotes(Session::Regular)
+    }
+
     pub fn metadata(&self) -> Result<YMetaData, YahooError> {
         self.check_consistency()?;
         let stock = &self.chart.result[0];
@@ -133,6 +279,53 @@ pub struct Quote {
     pub volume: u64,
     pub close: f64,
     pub adjclose: f64,
+    /// The trading session of the quote, if covered by the trading periods of the response
+    pub session: Option<Session>,
+}
+
+/// Struct for single quote, with the values missing in the response left empty
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct PartialQuote {
+    pub timestamp: u64,
+    pub open: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub volume: Option<u64>,
+    pub close: Option<f64>,
+    pub adjclose: Option<f64>,
+    /// The trading session of the quote, if covered by the trading periods of the response
+    pub session: Option<Session>,
+}
+
+impl PartialQuote {
+    fn is_complete(&self, with_adjclose: bool) -> bool {
+        self.open.is_some()
+            && self.high.is_some()
+            && self.low.is_some()
+            && self.volume.is_some()
+            && self.close.is_some()
+            && (!with_adjclose || self.adjclose.is_some())
+    }
+}
+
+/// The quotes of a response including the incomplete ones
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteReport {
+    /// The quotes of all the timestamps in the response
+    pub quotes: Vec<PartialQuote>,
+    /// The timestamps of the quotes without a close, skipped by [`YResponse::quotes`]
+    pub dropped: Vec<u64>,
+    /// The timestamps of the quotes with a close but some other values missing,
+    /// which [`YResponse::quotes`] sets to zero
+    pub partial: Vec<u64>,
+}
+
+/// The trading session a quote belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Session {
+    PreMarket,
+    Regular,
+    PostMarket,
 }
 
 #[derive(Deserialize, Debug)]
@@ -144,6 +337,8 @@ pub struct YChart {
 #[derive(Deserialize, Debug)]
 pub struct YQuoteBlock {
     pub meta: YMetaData,
+    // missing if there are no quotes in the requested period
+    #[serde(default)]
     pub timestamp: Vec<u64>,
     pub events: Option<EventsBlock>,
     pub indicators: QuoteBlock,
@@ -209,12 +404,17 @@ impl<'de> Deserialize<'de> for TradingPeriods {
             where
                 V: SeqAccess<'de>,
             {
-                let regular: Vec<PeriodInfo> = seq
-                    .next_element()?
-                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                // the regular periods of each day
+                let mut regular: Vec<Vec<PeriodInfo>> = Vec::new();
+                while let Some(day) = seq.next_element()? {
+                    regular.push(day);
+                }
+                if regular.is_empty() {
+                    return Err(de::Error::invalid_length(0, &self));
+                }
                 Ok(TradingPeriods {
                     pre: None,
-                    regular: Some(vec![regular]),
+                    regular: Some(regular),
                     post: None,
                 })
             }
@@ -256,6 +456,53 @@ impl<'de> Deserialize<'de> for TradingPeriods {
     }
 }
 
+impl TradingPeriods {
+    /// Return the session containing the given timestamp
+    pub fn session(&self, timestamp: u64) -> Option<Session> {
+        let contains = |days: &Option<Vec<Vec<PeriodInfo>>>| {
+            days.iter().flatten().flatten().any(|period| {
+                u64::from(period.start) <= timestamp && timestamp < u64::from(period.end)
+            })
+        };
+        if contains(&self.regular) {
+            Some(Session::Regular)
+        } else if contains(&self.pre) {
+            Some(Session::PreMarket)
+        } else if contains(&self.post) {
+            Some(Session::PostMarket)
+        } else {
+            None
+        }
+    }
+
+    /// Combine the trading periods of two responses, skipping the days contained in both.
+    fn merge(self, other: TradingPeriods) -> TradingPeriods {
+        fn merge_days(
+            days: Option<Vec<Vec<PeriodInfo>>>,
+            other: Option<Vec<Vec<PeriodInfo>>>,
+        ) -> Option<Vec<Vec<PeriodInfo>>> {
+            let mut days = match (days, other) {
+                (None, None) => return None,
+                (Some(days), None) | (None, Some(days)) => days,
+                (Some(mut days), Some(other)) => {
+                    days.extend(other);
+                    days
+                }
+            };
+            days.retain(|day| !day.is_empty());
+            days.sort_by_key(|day| day[0].start);
+            days.dedup_by_key(|day| day[0].start);
+            Some(days)
+        }
+
+        TradingPeriods {
+            pre: merge_days(self.pre, other.pre),
+            regular: merge_days(self.regular, other.regular),
+            post: merge_days(self.post, other.post),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct CurrentTradingPeriod {
     pub pre: PeriodInfo,
@@ -297,21 +544,42 @@ impl QuoteBlock {
             volume: quote.volume[i].unwrap_or(0),
             close: quote.close[i].unwrap(),
             adjclose: adjclose.unwrap_or(0.0),
+            session: None,
         })
     }
+
+    fn get_ith_partial_quote(&self, timestamp: u64, i: usize) -> PartialQuote {
+        let quote = &self.quote[0];
+        PartialQuote {
+            timestamp,
+            open: quote.open[i],
+            high: quote.high[i],
+            low: quote.low[i],
+            volume: quote.volume[i],
+            close: quote.close[i],
+            adjclose: self.adjclose.as_ref().and_then(|a| a[0].adjclose[i]),
+            session: None,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
 pub struct AdjClose {
+    #[serde(default)]
     adjclose: Vec<Option<f64>>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct QuoteList {
+    #[serde(default)]
     pub volume: Vec<Option<u64>>,
+    #[serde(default)]
     pub high: Vec<Option<f64>>,
+    #[serde(default)]
     pub close: Vec<Option<f64>>,
+    #[serde(default)]
     pub low: Vec<Option<f64>>,
+    #[serde(default)]
     pub open: Vec<Option<f64>>,
 }
 
@@ -323,6 +591,12 @@ pub struct EventsBlock {
     pub capital_gains: Option<HashMap<u64, CapitalGain>>,
 }
 
+fn merge_events<T>(events: &mut Option<HashMap<u64, T>>, new: Option<HashMap<u64, T>>) {
+    if let Some(new) = new {
+        events.get_or_insert_with(HashMap::new).extend(new);
+    }
+}
+
 /// This structure simply models a split that has occured.
 #[derive(Deserialize, Debug, Clone)]
 pub struct Split {
@@ -415,6 +689,29 @@ mod tests {
         assert_eq!(&trading_periods_expected, &trading_periods_deserialized);
     }
 
+    #[test]
+    fn test_deserialize_trading_periods_several_days() {
+        let trading_periods_json = r#"
+        [
+            [{"timezone": "EST", "start": 1705501800, "end": 1705525200, "gmtoffset": -18000}],
+            [{"timezone": "EST", "start": 1705588200, "end": 1705611600, "gmtoffset": -18000}]
+        ]
+        "#;
+        let trading_periods: TradingPeriods = serde_json::from_str(trading_periods_json).unwrap();
+        let days = trading_periods.regular.as_ref().unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[1][0].start, 1705588200);
+
+        // the quotes of the second day are tagged with the periods of the second day
+        assert_eq!(trading_periods.session(1705501800), Some(Session::Regular));
+        assert_eq!(trading_periods.session(1705588200), Some(Session::Regular));
+        assert_eq!(trading_periods.session(1705611540), Some(Session::Regular));
+        assert_eq!(trading_periods.session(1705611600), None);
+        assert_eq!(trading_periods.session(1705540000), None);
+
+        assert!(serde_json::from_str::<TradingPeriods>("[]").is_err());
+    }
+
     #[test]
     fn test_deserialize_trading_periods_complex_regular_only() {
         let trading_periods_json = r#"
@@ -506,4 +803,195 @@ mod tests {
             serde_json::from_str(trading_periods_json).unwrap();
         assert_eq!(&trading_periods_expected, &trading_periods_deserialized);
     }
+
+    fn window_json(timestamps: &[u64], closes: &[f64], events: &str) -> YResponse {
+        let list = |values: Vec<String>| values.join(", ");
+        let json = format!(
+            r#"{{"chart": {{"result": [{{
+                "meta": {{
+                    "currency": "USD", "symbol": "AAPL", "exchangeName": "NMS",
+                    "instrumentType": "EQUITY", "regularMarketTime": 1705525200,
+                    "gmtoffset": -18000, "timezone": "EST",
+                    "exchangeTimezoneName": "America/New_York", "regularMarketPrice": 1.0,
+                    "chartPreviousClose": 1.0, "priceHint": 2,
+                    "currentTradingPeriod": {{
+                        "pre": {{"timezone": "EST", "start": 1705482000, "end": 1705501800, "gmtoffset": -18000}},
+                        "regular": {{"timezone": "EST", "start": 1705501800, "end": 1705525200, "gmtoffset": -18000}},
+                        "post": {{"timezone": "EST", "start": 1705525200, "end": 1705539600, "gmtoffset": -18000}}
+                    }},
+                    "tradingPeriods": [[{{"timezone": "EST", "start": {start}, "end": {end}, "gmtoffset": -18000}}]],
+                    "dataGranularity": "1m", "range": "", "validRanges": ["1d", "5d"]
+                }},
+                "timestamp": [{timestamps}],
+                {events}
+                "indicators": {{
+                    "quote": [{{"volume": [{volumes}], "high": [{closes}], "close": [{closes}], "low": [{closes}], "open": [{closes}]}}],
+                    "adjclose": [{{"adjclose": [{closes}]}}]
+                }}
+            }}], "error": null}}}}"#,
+            start = timestamps[0],
+            end = timestamps[timestamps.len() - 1],
+            timestamps = list(timestamps.iter().map(|t| t.to_string()).collect()),
+            volumes = list(timestamps.iter().map(|_| "100".to_string()).collect()),
+            closes = list(closes.iter().map(|c| c.to_string()).collect()),
+            events = events,
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_merge() {
+        let first = window_json(
+            &[100, 160, 220],
+            &[1.0, 2.0, 3.0],
+            r#""events": {"dividends": {"160": {"amount": 0.5, "date": 160}}},"#,
+        );
+        let second = window_json(
+            &[220, 280],
+            &[3.5, 4.0],
+            r#""events": {"splits": {"280": {"date": 280, "numerator": 4, "denominator": 1, "splitRatio": "4:1"}}},"#,
+        );
+        let empty: YResponse = serde_json::from_str(
+            r#"{"chart": {"result": [{"meta": {
+                "currency": "USD", "symbol": "AAPL", "exchangeName": "NMS",
+                "instrumentType": "EQUITY", "regularMarketTime": 1705525200,
+                "gmtoffset": -18000, "timezone": "EST",
+                "exchangeTimezoneName": "America/New_York", "regularMarketPrice": 1.0,
+                "chartPreviousClose": 1.0, "priceHint": 2,
+                "currentTradingPeriod": {
+                    "pre": {"timezone": "EST", "start": 1705482000, "end": 1705501800, "gmtoffset": -18000},
+                    "regular": {"timezone": "EST", "start": 1705501800, "end": 1705525200, "gmtoffset": -18000},
+                    "post": {"timezone": "EST", "start": 1705525200, "end": 1705539600, "gmtoffset": -18000}
+                },
+                "dataGranularity": "1m", "range": "", "validRanges": ["1d", "5d"]
+            }, "indicators": {"quote": [{}], "adjclose": [{}]}}], "error": null}}"#,
+        )
+        .unwrap();
+
+        // the order of the responses doesn't matter, except for the duplicates
+        let merged = YResponse::merge(vec![second, empty, first]).unwrap();
+        let quotes = merged.quotes().unwrap();
+        assert_eq!(
+            quotes.iter().map(|q| q.timestamp).collect::<Vec<_>>(),
+            vec![100, 160, 220, 280]
+        );
+        assert_eq!(
+            quotes.iter().map(|q| q.close).collect::<Vec<_>>(),
+            vec![1.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(merged.dividends().unwrap()[0].date, 160);
+        assert_eq!(merged.splits().unwrap()[0].numerator, 4.0);
+
+        let regular = merged.metadata().unwrap().trading_periods.regular.unwrap();
+        assert_eq!(
+            regular.iter().map(|day| day[0].start).collect::<Vec<_>>(),
+            vec![100, 220]
+        );
+
+        assert!(matches!(
+            YResponse::merge(vec![]),
+            Err(YahooError::EmptyDataSet)
+        ));
+    }
+
+    #[test]
+    fn test_sessions() {
+        let period = |start, end| {
+            Some(vec![vec![PeriodInfo {
+                timezone: "EST".to_string(),
+                start,
+                end,
+                gmtoffset: -18000,
+            }]])
+        };
+        let mut response = window_json(
+            &[
+                1705482000, 1705501740, 1705501800, 1705525140, 1705525200, 1705539600,
+            ],
+            &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            "",
+        );
+        response.chart.result[0].meta.trading_periods = TradingPeriods {
+            pre: period(1705482000, 1705501800),
+            regular: period(1705501800, 1705525200),
+            post: period(1705525200, 1705539600),
+        };
+
+        let sessions = response
+            .quotes()
+            .unwrap()
+            .iter()
+            .map(|q| q.session)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sessions,
+            vec![
+                Some(Session::PreMarket),
+                Some(Session::PreMarket),
+                Some(Session::Regular),
+                Some(Session::Regular),
+                Some(Session::PostMarket),
+                None,
+            ]
+        );
+        assert_eq!(
+            response
+                .regular_quotes()
+                .unwrap()
+                .iter()
+                .map(|q| q.close)
+                .collect::<Vec<_>>(),
+            vec![3.0, 4.0]
+        );
+        assert_eq!(
+            response.session_quotes(Session::PostMarket).unwrap().len(),
+            1
+        );
+        assert_eq!(response.last_quote().unwrap().session, None);
+    }
+
+    #[test]
+    fn test_partial_quotes() {
+        let json = r#"{"chart": {"result": [{
+            "meta": {
+                "currency": "USD", "symbol": "AAPL", "exchangeName": "NMS",
+                "instrumentType": "EQUITY", "regularMarketTime": 1705525200,
+                "gmtoffset": -18000, "timezone": "EST",
+                "exchangeTimezoneName": "America/New_York", "regularMarketPrice": 1.0,
+                "chartPreviousClose": 1.0, "priceHint": 2,
+                "currentTradingPeriod": {
+                    "pre": {"timezone": "EST", "start": 1705482000, "end": 1705501800, "gmtoffset": -18000},
+                    "regular": {"timezone": "EST", "start": 1705501800, "end": 1705525200, "gmtoffset": -18000},
+                    "post": {"timezone": "EST", "start": 1705525200, "end": 1705539600, "gmtoffset": -18000}
+                },
+                "dataGranularity": "1d", "range": "", "validRanges": ["1d", "5d"]
+            },
+            "timestamp": [100, 200, 300, 400],
+            "indicators": {
+                "quote": [{
+                    "volume": [10, null, 0, null],
+                    "high": [1.5, 2.5, 3.5, null],
+                    "close": [1.0, 2.0, 3.0, null],
+                    "low": [0.5, 1.5, 2.5, null],
+                    "open": [1.0, 2.0, 3.0, null]
+                }],
+                "adjclose": [{"adjclose": [1.0, 2.0, null, null]}]
+            }
+        }], "error": null}}"#;
+        let response: YResponse = serde_json::from_str(json).unwrap();
+
+        let report = response.partial_quotes().unwrap();
+        assert_eq!(report.quotes.len(), 4);
+        assert_eq!(report.dropped, vec![400]);
+        assert_eq!(report.partial, vec![200, 300]);
+        assert_eq!(report.quotes[1].volume, None);
+        assert_eq!(report.quotes[2].volume, Some(0));
+        assert_eq!(report.quotes[2].adjclose, None);
+        assert_eq!(report.quotes[3].close, None);
+
+        // the complete extraction fills the gaps with zeros and skips the last quote
+        let quotes = response.quotes().unwrap();
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[1].volume, 0);
+    }
 }
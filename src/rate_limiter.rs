@@ -0,0 +1,129 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// Token bucket limiting the rate of the requests sent to yahoo! finance.
+///
+/// Every request takes a token from the bucket, which is refilled at the configured rate up
+/// to the burst size. When the bucket is empty, the requests wait for their turn.
+///
+/// Wrap the limiter in an `Arc` to share it between multiple connectors.
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    // negative when the tokens are already promised to the waiting requests
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing the given number of requests per second on average
+    /// and at most `burst` requests at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests_per_second` is not a positive number.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second > 0.0,
+            "the rate of requests must be positive"
+        );
+        let burst = f64::from(burst.max(1));
+        RateLimiter {
+            requests_per_second,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                updated_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until the next request can be sent.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst);
+            bucket.updated_at = now;
+
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / self.requests_per_second)
+        };
+
+        log::trace!("rate limit reached, waiting {:?}", wait);
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_then_rate() {
+        let limiter = RateLimiter::new(10.0, 2);
+        let start = Instant::now();
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+
+        // the bucket refills while idle, but only up to the burst size
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let idle = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(idle.elapsed(), Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrent_waiters() {
+        let limiter = std::sync::Arc::new(RateLimiter::new(2.0, 1));
+        let start = Instant::now();
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    limiter.acquire().await;
+                    start.elapsed()
+                })
+            })
+            .collect();
+
+        let mut elapsed = vec![];
+        for task in tasks {
+            elapsed.push(task.await.unwrap());
+        }
+        elapsed.sort();
+
+        assert_eq!(
+            elapsed,
+            vec![
+                Duration::ZERO,
+                Duration::from_millis(500),
+                Duration::from_millis(1000),
+                Duration::from_millis(1500),
+            ]
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use time::OffsetDateTime;
+
+use crate::quote_summary::QuoteSummary;
+use crate::YahooError;
+
+/// The analyst recommendations for a symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommendations {
+    /// The number of recommendations per month, starting with the current one
+    pub trend: Vec<RecommendationCounts>,
+    /// The rating changes by the analyst firms, oldest first
+    pub rating_changes: Vec<RatingChange>,
+}
+
+/// The number of analysts recommending each action during a period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecommendationCounts {
+    /// The period relative to the current month, e.g. `0m` or `-1m`
+    pub period: String,
+    pub strong_buy: u32,
+    pub buy: u32,
+    pub hold: u32,
+    pub sell: u32,
+    pub strong_sell: u32,
+}
+
+impl RecommendationCounts {
+    pub fn total(&self) -> u32 {
+        self.strong_buy + self.buy + self.hold + self.sell + self.strong_sell
+    }
+
+    /// The average recommendation from 1 (strong buy) to 5 (strong sell), `None` without
+    /// recommendations
+    pub fn mean(&self) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let sum =
+            self.strong_buy + 2 * self.buy + 3 * self.hold + 4 * self.sell + 5 * self.strong_sell;
+        Some(sum as f64 / total as f64)
+    }
+}
+
+/// What an analyst firm did to its rating
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RatingAction {
+    Upgrade,
+    Downgrade,
+    Maintain,
+    Initiate,
+    Reiterate,
+    /// An action yahoo! finance reports which is not known to this crate
+    Other(String),
+}
+
+impl RatingAction {
+    fn parse(action: &str) -> Self {
+        match action {
+            "up" => RatingAction::Upgrade,
+            "down" => RatingAction::Downgrade,
+            "main" => RatingAction::Maintain,
+            "init" => RatingAction::Initiate,
+            "reit" => RatingAction::Reiterate,
+            other => RatingAction::Other(other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatingChange {
+    pub date: OffsetDateTime,
+    pub firm: String,
+    pub action: RatingAction,
+    /// The previous grade, `None` if the firm didn't rate the symbol before
+    pub from_grade: Option<String>,
+    pub to_grade: Option<String>,
+}
+
+impl Recommendations {
+    /// Collect the recommendations from the `recommendationTrend` and `upgradeDowngradeHistory`
+    /// modules of the quote summary.
+    pub(crate) fn from_summary(summary: QuoteSummary) -> Result<Self, YahooError> {
+        if summary.recommendation_trend.is_none() && summary.upgrade_downgrade_history.is_none() {
+            return Err(YahooError::EmptyDataSet);
+        }
+
+        let trend = summary
+            .recommendation_trend
+            .and_then(|t| t.trend)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|t| {
+                Some(RecommendationCounts {
+                    period: t.period?,
+                    strong_buy: t.strong_buy.unwrap_or(0),
+                    buy: t.buy.unwrap_or(0),
+                    hold: t.hold.unwrap_or(0),
+                    sell: t.sell.unwrap_or(0),
+                    strong_sell: t.strong_sell.unwrap_or(0),
+                })
+            })
+            .collect();
+
+        let mut rating_changes = vec![];
+        for change in summary
+            .upgrade_downgrade_history
+            .and_then(|h| h.history)
+            .unwrap_or_default()
+        {
+            let (Some(date), Some(firm)) = (change.epoch_grade_date, change.firm) else {
+                continue;
+            };
+            let date = OffsetDateTime::from_unix_timestamp(date)
+                .map_err(|_| YahooError::DataInconsistency)?;
+            rating_changes.push(RatingChange {
+                date,
+                firm,
+                action: RatingAction::parse(change.action.as_deref().unwrap_or_default()),
+                from_grade: change.from_grade.filter(|g| !g.is_empty()),
+                to_grade: change.to_grade.filter(|g| !g.is_empty()),
+            });
+        }
+        rating_changes.sort_by_key(|c| c.date);
+
+        Ok(Recommendations {
+            trend,
+            rating_changes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::quote_summary::from_response;
+
+    const RECOMMENDATIONS: &str = r#"
+    {
+        "quoteSummary": {
+            "result": [
+                {
+                    "recommendationTrend": {
+                        "trend": [
+                            {"period": "0m", "strongBuy": 8, "buy": 24, "hold": 12, "sell": 1, "strongSell": 2},
+                            {"period": "-1m", "strongBuy": 8, "buy": 23, "hold": 13, "sell": 1, "strongSell": 2},
+                            {"period": "-2m", "strongBuy": 0, "buy": 0, "hold": 0, "sell": 0, "strongSell": 0}
+                        ],
+                        "maxAge": 86400
+                    },
+                    "upgradeDowngradeHistory": {
+                        "history": [
+                            {"epochGradeDate": 1730419200, "firm": "Maxim Group", "toGrade": "Buy", "fromGrade": "Hold", "action": "up"},
+                            {"epochGradeDate": 1730332800, "firm": "Wedbush", "toGrade": "Outperform", "fromGrade": "", "action": "init"},
+                            {"epochGradeDate": 1730505600, "firm": "Jefferies", "toGrade": "Hold", "fromGrade": "Buy", "action": "down"},
+                            {"epochGradeDate": 1730505600, "firm": "Evercore", "toGrade": "Outperform", "fromGrade": "Outperform", "action": "pt"}
+                        ],
+                        "maxAge": 86400
+                    }
+                }
+            ],
+            "error": null
+        }
+    }
+    "#;
+
+    #[test]
+    fn test_from_summary() {
+        let summary = from_response(serde_json::from_str(RECOMMENDATIONS).unwrap()).unwrap();
+        let recommendations = Recommendations::from_summary(summary).unwrap();
+
+        let trend = &recommendations.trend;
+        assert_eq!(trend.len(), 3);
+        assert_eq!(trend[0].period, "0m");
+        assert_eq!(trend[0].total(), 47);
+        assert_eq!(trend[1].hold, 13);
+        assert_eq!(trend[2].mean(), None);
+        let mean = trend[0].mean().unwrap();
+        assert!((mean - 106.0 / 47.0).abs() < 1e-12);
+
+        let changes = &recommendations.rating_changes;
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes[0].firm, "Wedbush");
+        assert_eq!(changes[0].date, datetime!(2024-10-31 0:00:00 UTC));
+        assert_eq!(changes[0].action, RatingAction::Initiate);
+        assert_eq!(changes[0].from_grade, None);
+        assert_eq!(changes[1].action, RatingAction::Upgrade);
+        assert_eq!(changes[1].from_grade.as_deref(), Some("Hold"));
+        assert_eq!(changes[1].to_grade.as_deref(), Some("Buy"));
+        assert_eq!(changes[2].action, RatingAction::Downgrade);
+        assert_eq!(changes[3].action, RatingAction::Other("pt".to_string()));
+        assert_eq!(changes[3].date, datetime!(2024-11-02 0:00:00 UTC));
+    }
+}
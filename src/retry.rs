@@ -0,0 +1,304 @@
+use std::convert::TryFrom;
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+use time::format_description::well_known::Rfc2822;
+use time::OffsetDateTime;
+use wreq::header::{HeaderMap, RETRY_AFTER};
+
+use crate::YahooError;
+
+/// Policy for retrying the requests that failed for a transient reason.
+///
+/// The requests are retried if the server reports too many requests, if the connection
+/// fails or if the server responds with a 5xx status. The delay before the next attempt grows
+/// exponentially from `backoff_base` up to `backoff_cap`. The server can ask for a longer delay
+/// using the `Retry-After` header, which is also limited to `backoff_cap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one
+    pub max_attempts: u8,
+    /// The delay after the first failed attempt
+    pub backoff_base: Duration,
+    /// The maximum delay between two attempts, also if the server asks for a longer one
+    pub backoff_cap: Duration,
+    /// Randomize the delays so that concurrent clients don't retry all at once
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff_base: Duration::from_millis(500),
+            backoff_cap: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create the default policy with the given maximum number of attempts.
+    pub fn new(max_attempts: u8) -> Self {
+        RetryPolicy {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    pub fn backoff(mut self, base: Duration, cap: Duration) -> Self {
+        self.backoff_base = base;
+        self.backoff_cap = cap;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The delay before the attempt following the given (1-based) failed one.
+    pub(crate) fn delay(&self, failed_attempt: u8) -> Duration {
+        let factor = 2u32.saturating_pow(u32::from(failed_attempt.saturating_sub(1)));
+        let delay = self
+            .backoff_base
+            .checked_mul(factor)
+            .unwrap_or(self.backoff_cap)
+            .min(self.backoff_cap);
+
+        if self.jitter && !delay.is_zero() {
+            // keep at least the half of the delay so that the backoff still grows
+            let half = delay / 2;
+            half + rand::rng().random_range(Duration::ZERO..=half)
+        } else {
+            delay
+        }
+    }
+
+    /// The delay before the attempt following the given failed one, taking the delay requested
+    /// by the server into account.
+    fn delay_after(&self, failed_attempt: u8, retry_after: Option<Duration>) -> Duration {
+        let delay = self.delay(failed_attempt);
+        match retry_after {
+            Some(retry_after) => retry_after.max(delay).min(self.backoff_cap),
+            None => delay,
+        }
+    }
+}
+
+/// A failed attempt of a request.
+pub(crate) struct Failure {
+    pub error: YahooError,
+    pub retryable: bool,
+    pub retry_after: Option<Duration>,
+}
+
+impl From<YahooError> for Failure {
+    fn from(error: YahooError) -> Self {
+        let retryable = matches!(
+            error,
+            YahooError::TooManyRequests(..) | YahooError::ConnectionFailed(_)
+        );
+        Failure {
+            error,
+            retryable,
+            retry_after: None,
+        }
+    }
+}
+
+/// Run the operation until it succeeds, fails for a non-transient reason or until the policy
+/// runs out of attempts. Without a policy, the operation is attempted just once.
+pub(crate) async fn retry<T, F, Fut>(
+    policy: Option<&RetryPolicy>,
+    mut op: F,
+) -> Result<T, YahooError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Failure>>,
+{
+    let max_attempts = policy.map(|p| p.max_attempts.max(1)).unwrap_or(1);
+    let mut attempt = 1;
+    loop {
+        let failure = match op().await {
+            Ok(res) => return Ok(res),
+            Err(failure) => failure,
+        };
+
+        let policy = match policy {
+            Some(policy) if failure.retryable => policy,
+            _ => return Err(failure.error),
+        };
+
+        if attempt >= max_attempts {
+            if max_attempts == 1 {
+                return Err(failure.error);
+            }
+            return Err(YahooError::MaxRetriesReached(
+                attempt,
+                Box::new(failure.error),
+            ));
+        }
+
+        let delay = policy.delay_after(attempt, failure.retry_after);
+
+        log::trace!(
+            "attempt {} failed with: {}, retrying in {:?}",
+            attempt,
+            failure.error,
+            delay
+        );
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Read the delay requested by the server in the `Retry-After` header, given either in
+/// seconds or as a date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    let delay = date - OffsetDateTime::now_utc();
+    Some(Duration::try_from(delay).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use wreq::header::HeaderValue;
+
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new(3)
+            .backoff(Duration::from_millis(1), Duration::from_millis(3))
+            .jitter(false)
+    }
+
+    fn failure(retryable: bool) -> Failure {
+        Failure {
+            error: YahooError::FetchFailed("status 503".into()),
+            retryable,
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy::new(10)
+            .backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .jitter(false);
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(4), Duration::from_millis(800));
+        assert_eq!(policy.delay(5), Duration::from_secs(1));
+        assert_eq!(policy.delay(200), Duration::from_secs(1));
+
+        let policy = policy.jitter(true);
+        for _ in 0..100 {
+            let delay = policy.delay(3);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+        }
+    }
+
+    #[test]
+    fn test_delay_after() {
+        let policy = RetryPolicy::new(10)
+            .backoff(Duration::from_millis(100), Duration::from_secs(30))
+            .jitter(false);
+        assert_eq!(policy.delay_after(2, None), Duration::from_millis(200));
+        assert_eq!(
+            policy.delay_after(2, Some(Duration::from_millis(50))),
+            Duration::from_millis(200)
+        );
+        assert_eq!(
+            policy.delay_after(2, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+        // a server asking to wait for a day is limited to the cap
+        assert_eq!(
+            policy.delay_after(2, Some(Duration::from_secs(86400))),
+            Duration::from_secs(30)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let attempts = Cell::new(0);
+        let res = retry(Some(&policy()), || async {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(failure(true))
+            } else {
+                Ok(attempts.get())
+            }
+        })
+        .await;
+        assert_eq!(res.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausted() {
+        let attempts = Cell::new(0);
+        let res: Result<(), _> = retry(Some(&policy()), || async {
+            attempts.set(attempts.get() + 1);
+            Err(failure(true))
+        })
+        .await;
+        assert_eq!(attempts.get(), 3);
+        assert!(matches!(
+            res,
+            Err(YahooError::MaxRetriesReached(3, ref e)) if matches!(**e, YahooError::FetchFailed(_))
+        ));
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "request didn't succeed in 3 attempts"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_retry() {
+        let attempts = Cell::new(0);
+        let res: Result<(), _> = retry(Some(&policy()), || async {
+            attempts.set(attempts.get() + 1);
+            Err(failure(false))
+        })
+        .await;
+        assert_eq!(attempts.get(), 1);
+        assert!(matches!(res, Err(YahooError::FetchFailed(_))));
+
+        let res: Result<(), _> = retry(None, || async {
+            attempts.set(attempts.get() + 1);
+            Err(failure(true))
+        })
+        .await;
+        assert_eq!(attempts.get(), 2);
+        assert!(matches!(res, Err(YahooError::FetchFailed(_))));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+}
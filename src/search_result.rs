@@ -1,8 +1,10 @@
-use select::document::Document;
-use select::predicate::{Class, Name};
+use std::convert::TryFrom;
+
 use serde::Deserialize;
+use time::OffsetDateTime;
 
 use super::YahooError;
+use crate::options::{OptionChain, OptionContract};
 
 #[derive(Deserialize, Debug)]
 pub struct YSearchResultOpt {
@@ -116,65 +118,119 @@ impl YSearchResult {
 pub struct YOptionResult {
     pub name: String,
     pub strike: f64,
-    pub last_trade_date: String,
+    pub last_trade_date: OffsetDateTime,
     pub last_price: f64,
     pub bid: f64,
     pub ask: f64,
     pub change: f64,
+    /// The change in percent
     pub change_pct: f64,
     pub volume: i32,
     pub open_interest: i32,
+    /// The implied volatility in percent
     pub impl_volatility: f64,
 }
 
+impl TryFrom<&OptionContract> for YOptionResult {
+    type Error = YahooError;
+
+    fn try_from(contract: &OptionContract) -> Result<Self, Self::Error> {
+        let invalid = |field: &str, value: &dyn std::fmt::Display| {
+            YahooError::FetchFailed(format!(
+                "invalid {} {} of contract {}",
+                field, value, contract.contract_symbol
+            ))
+        };
+        let open_interest = contract.open_interest;
+        if open_interest.fract() != 0.0 || open_interest < 0.0 || open_interest > i32::MAX as f64 {
+            return Err(invalid("open interest", &open_interest));
+        }
+
+        Ok(YOptionResult {
+            name: contract.contract_symbol.clone(),
+            strike: contract.strike,
+            last_trade_date: contract.last_trade_date,
+            last_price: contract.last_price,
+            bid: contract.bid,
+            ask: contract.ask,
+            change: contract.change,
+            change_pct: contract.percent_change,
+            volume: i32::try_from(contract.volume)
+                .map_err(|_| invalid("volume", &contract.volume))?,
+            open_interest: open_interest as i32,
+            impl_volatility: contract.implied_volatility * 100.0,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct YOptionResults {
     pub options: Vec<YOptionResult>,
 }
 
 impl YOptionResults {
-    pub fn scrape(http_res: &str) -> Self {
-        let document = Document::from(http_res);
-
-        if let Some(table) = document.find(Class("list-options")).next() {
-            let rows = table.find(Name("tr"));
-            let options = rows
-                .skip(1)
-                .map(|row| {
-                    let columns = row.find(Name("td"));
-                    let cols: Vec<String> = columns
-                        .take(11)
-                        .map(|s| s.text().trim().to_owned())
-                        .collect();
-                    cols
-                })
-                .map(|sv| YOptionResult {
-                    name: sv[0].clone(),
-                    last_trade_date: sv[1].clone(),
-                    strike: sv[2].replace(',', "").parse::<f64>().unwrap_or(0.0),
-                    last_price: sv[3].replace(',', "").parse::<f64>().unwrap_or(0.0),
-                    bid: sv[4].replace(',', "").parse::<f64>().unwrap_or(0.0),
-                    ask: sv[5].replace(',', "").parse::<f64>().unwrap_or(0.0),
-                    change: sv[6].replace(',', "").parse::<f64>().unwrap_or(0.0),
-                    change_pct: sv[7]
-                        .replace(',', "")
-                        .trim_end_matches('%')
-                        .parse::<f64>()
-                        .unwrap_or(0.0),
-                    volume: sv[8].replace(',', "").parse::<i32>().unwrap_or(0),
-                    open_interest: sv[9].replace(',', "").parse::<i32>().unwrap_or(0),
-                    impl_volatility: sv[10]
-                        .replace(',', "")
-                        .trim_end_matches('%')
-                        .parse::<f64>()
-                        .unwrap_or(0.0),
-                })
-                .collect();
-            Self { options }
-        } else {
-            Self {
-                options: Vec::new(),
-            }
-        }
+    /// The calls followed by the puts of the option chain
+    pub fn from_chain(chain: &OptionChain) -> Result<Self, YahooError> {
+        let options = chain
+            .calls
+            .iter()
+            .chain(chain.puts.iter())
+            .map(YOptionResult::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { options })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(open_interest: f64) -> OptionChain {
+        let contract = |symbol: &str| {
+            serde_json::json!({
+                "contractSymbol": symbol,
+                "strike": 230.0,
+                "currency": "USD",
+                "lastPrice": 3.25,
+                "change": -0.5,
+                "percentChange": -13.33,
+                "volume": 1520,
+                "openInterest": open_interest,
+                "bid": 3.2,
+                "ask": 3.3,
+                "contractSize": "REGULAR",
+                "expiration": 1733443200,
+                "lastTradeDate": 1732299649,
+                "impliedVolatility": 0.2451,
+                "inTheMoney": false
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "expirationDate": 1733443200,
+            "hasMiniOptions": false,
+            "calls": [contract("AAPL241206C00230000")],
+            "puts": [contract("AAPL241206P00230000")]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_chain() {
+        let results = YOptionResults::from_chain(&chain(4410.0)).unwrap();
+        assert_eq!(results.options.len(), 2);
+        let call = &results.options[0];
+        assert_eq!(call.name, "AAPL241206C00230000");
+        assert_eq!(call.last_trade_date.unix_timestamp(), 1732299649);
+        assert_eq!(call.change_pct, -13.33);
+        assert_eq!(call.volume, 1520);
+        assert_eq!(call.open_interest, 4410);
+        assert!((call.impl_volatility - 24.51).abs() < 1e-9);
+        assert_eq!(results.options[1].name, "AAPL241206P00230000");
+
+        assert!(matches!(
+            YOptionResults::from_chain(&chain(1e12)),
+            Err(YahooError::FetchFailed(_))
+        ));
+        assert!(YOptionResults::from_chain(&chain(-1.0)).is_err());
     }
 }
@@ -0,0 +1,561 @@
+//! Real-time quotes streamed by yahoo! finance over a websocket.
+//!
+//! The streamer pushes a base64 encoded protobuf [`PricingData`] message for every change
+//! of the subscribed symbols. Use [`YahooConnector::stream_quotes`] to connect to it.
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use base64::Engine as _;
+use futures::stream::{self, Stream};
+use prost::Message as _;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use wreq::ws::message::Message;
+use wreq::ws::WebSocket;
+use wreq::Client;
+
+use crate::retry::{self, Failure};
+use crate::{RateLimiter, RetryPolicy, YahooConnector, YahooError};
+
+/// A change of the price of a subscribed symbol
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PricingData {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(float, tag = "2")]
+    pub price: f32,
+    /// Milliseconds since the unix epoch
+    #[prost(sint64, tag = "3")]
+    pub time: i64,
+    #[prost(string, tag = "4")]
+    pub currency: String,
+    #[prost(string, tag = "5")]
+    pub exchange: String,
+    #[prost(enumeration = "QuoteType", tag = "6")]
+    pub quote_type: i32,
+    #[prost(enumeration = "MarketHoursType", tag = "7")]
+    pub market_hours: i32,
+    #[prost(float, tag = "8")]
+    pub change_percent: f32,
+    #[prost(sint64, tag = "9")]
+    pub day_volume: i64,
+    #[prost(float, tag = "10")]
+    pub day_high: f32,
+    #[prost(float, tag = "11")]
+    pub day_low: f32,
+    #[prost(float, tag = "12")]
+    pub change: f32,
+    #[prost(string, tag = "13")]
+    pub short_name: String,
+    #[prost(sint64, tag = "14")]
+    pub expire_date: i64,
+    #[prost(float, tag = "15")]
+    pub open_price: f32,
+    #[prost(float, tag = "16")]
+    pub previous_close: f32,
+    #[prost(float, tag = "17")]
+    pub strike_price: f32,
+    #[prost(string, tag = "18")]
+    pub underlying_symbol: String,
+    #[prost(sint64, tag = "19")]
+    pub open_interest: i64,
+    #[prost(enumeration = "OptionType", tag = "20")]
+    pub options_type: i32,
+    #[prost(sint64, tag = "21")]
+    pub mini_option: i64,
+    #[prost(sint64, tag = "22")]
+    pub last_size: i64,
+    #[prost(float, tag = "23")]
+    pub bid: f32,
+    #[prost(sint64, tag = "24")]
+    pub bid_size: i64,
+    #[prost(float, tag = "25")]
+    pub ask: f32,
+    #[prost(sint64, tag = "26")]
+    pub ask_size: i64,
+    #[prost(sint64, tag = "27")]
+    pub price_hint: i64,
+    #[prost(sint64, tag = "28")]
+    pub vol_24hr: i64,
+    #[prost(sint64, tag = "29")]
+    pub vol_all_currencies: i64,
+    #[prost(string, tag = "30")]
+    pub from_currency: String,
+    #[prost(string, tag = "31")]
+    pub last_market: String,
+    #[prost(double, tag = "32")]
+    pub circulating_supply: f64,
+    #[prost(double, tag = "33")]
+    pub market_cap: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum QuoteType {
+    None = 0,
+    AltSymbol = 5,
+    Heartbeat = 7,
+    Equity = 8,
+    Index = 9,
+    MutualFund = 11,
+    MoneyMarket = 12,
+    Option = 13,
+    Currency = 14,
+    Warrant = 15,
+    Bond = 17,
+    Future = 18,
+    Etf = 20,
+    Commodity = 23,
+    EcnQuote = 28,
+    Cryptocurrency = 41,
+    Indicator = 42,
+    Industry = 1000,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum MarketHoursType {
+    PreMarket = 0,
+    RegularMarket = 1,
+    PostMarket = 2,
+    ExtendedHoursMarket = 3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum OptionType {
+    Call = 0,
+    Put = 1,
+}
+
+/// Stream of the price changes of the subscribed symbols.
+///
+/// The stream has to be polled within a tokio runtime, also with the `blocking` feature.
+///
+/// The connection is opened when the stream is polled for the first time. If it is lost, the
+/// stream waits for the backoff of the retry policy of the connector, reconnects and subscribes
+/// to all the symbols again. The attempts to connect are retried according to the same policy.
+/// The stream yields the error and ends once the attempts are exhausted, or once the connection
+/// is lost `max_attempts` times in a row without receiving a message.
+pub struct QuoteStream {
+    commands: mpsc::UnboundedSender<Command>,
+    ticks: Pin<Box<dyn Stream<Item = Result<PricingData, YahooError>> + Send>>,
+}
+
+enum Command {
+    Subscribe(Vec<String>),
+    Unsubscribe(Vec<String>),
+}
+
+impl QuoteStream {
+    /// Add the symbols to the subscription
+    pub fn subscribe(&self, symbols: &[&str]) {
+        let symbols = symbols.iter().map(|s| s.to_string()).collect();
+        // the receiver lives as long as the stream itself
+        let _ = self.commands.send(Command::Subscribe(symbols));
+    }
+
+    /// Remove the symbols from the subscription
+    pub fn unsubscribe(&self, symbols: &[&str]) {
+        let symbols = symbols.iter().map(|s| s.to_string()).collect();
+        let _ = self.commands.send(Command::Unsubscribe(symbols));
+    }
+}
+
+impl Stream for QuoteStream {
+    type Item = Result<PricingData, YahooError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.ticks.as_mut().poll_next(cx)
+    }
+}
+
+impl YahooConnector {
+    /// Stream the price changes of the given symbols in real time
+    pub fn stream_quotes(&self, symbols: &[&str]) -> QuoteStream {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let connection = Connection {
+            client: self.client.clone(),
+            url: self.urls.streamer.clone(),
+            retry_policy: self.retry_policy.clone().unwrap_or_default(),
+            rate_limiter: self.rate_limiter.clone(),
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            commands: receiver,
+            socket: None,
+            lost_connections: 0,
+            finished: false,
+        };
+
+        let ticks = stream::unfold(connection, |mut connection| async move {
+            let tick = connection.next().await?;
+            Some((tick, connection))
+        });
+
+        QuoteStream {
+            commands,
+            ticks: Box::pin(ticks),
+        }
+    }
+}
+
+struct Connection {
+    client: Client,
+    url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    symbols: BTreeSet<String>,
+    commands: mpsc::UnboundedReceiver<Command>,
+    socket: Option<WebSocket>,
+    /// The number of times the connection was lost since the last message
+    lost_connections: u8,
+    finished: bool,
+}
+
+impl Connection {
+    async fn next(&mut self) -> Option<Result<PricingData, YahooError>> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            let socket = match self.socket {
+                Some(ref mut socket) => socket,
+                None => {
+                    match self.connect().await {
+                        Ok(socket) => self.socket = Some(socket),
+                        Err(e) => {
+                            self.finished = true;
+                            return Some(Err(e));
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            let res = tokio::select! {
+                command = self.commands.recv() => match command {
+                    Some(command) => self.apply(command).await,
+                    None => return None,
+                },
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        self.lost_connections = 0;
+                        match decode(text.as_str()) {
+                            Ok(Some(tick)) => return Some(Ok(tick)),
+                            Ok(None) => Ok(()),
+                            Err(e) => return Some(Err(e)),
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => {
+                        self.lost(YahooError::FetchFailed(
+                            "the streamer closed the connection".into(),
+                        ))
+                        .await
+                    }
+                    Some(Ok(_)) => Ok(()),
+                    Some(Err(e)) => {
+                        self.lost(YahooError::from_wreq_while(e, "receiving from the streamer"))
+                            .await
+                    }
+                },
+            };
+            if let Err(e) = res {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+    }
+
+    /// Drop the lost connection and wait before reconnecting, failing once the connection was
+    /// lost `max_attempts` times in a row.
+    async fn lost(&mut self, error: YahooError) -> Result<(), YahooError> {
+        self.socket = None;
+        self.lost_connections = self.lost_connections.saturating_add(1);
+        if self.lost_connections >= self.retry_policy.max_attempts.max(1) {
+            return Err(YahooError::MaxRetriesReached(
+                self.lost_connections,
+                Box::new(error),
+            ));
+        }
+
+        let delay = self.retry_policy.delay(self.lost_connections);
+        log::trace!("{}, reconnecting in {:?}", error, delay);
+        tokio::time::sleep(delay).await;
+        Ok(())
+    }
+
+    async fn connect(&self) -> Result<WebSocket, YahooError> {
+        retry::retry(Some(&self.retry_policy), || async {
+            self.open().await.map_err(|error| Failure {
+                error,
+                retryable: true,
+                retry_after: None,
+            })
+        })
+        .await
+    }
+
+    async fn open(&self) -> Result<WebSocket, YahooError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        let mut socket = self
+            .client
+            .websocket(&self.url)
+            .send()
+            .await
+            .map_err(|e| YahooError::from_wreq_while(e, "connecting to the streamer"))?
+            .into_websocket()
+            .await
+            .map_err(|e| YahooError::from_wreq_while(e, "connecting to the streamer"))?;
+
+        if !self.symbols.is_empty() {
+            let symbols: Vec<&String> = self.symbols.iter().collect();
+            send(&mut socket, "subscribe", &symbols).await?;
+        }
+        Ok(socket)
+    }
+
+    async fn apply(&mut self, command: Command) -> Result<(), YahooError> {
+        let (action, symbols) = match command {
+            Command::Subscribe(symbols) => {
+                self.symbols.extend(symbols.iter().cloned());
+                ("subscribe", symbols)
+            }
+            Command::Unsubscribe(symbols) => {
+                for symbol in &symbols {
+                    self.symbols.remove(symbol);
+                }
+                ("unsubscribe", symbols)
+            }
+        };
+
+        // without a connection, the symbols are subscribed to once connected
+        if let Some(socket) = &mut self.socket {
+            if let Err(e) = send(socket, action, &symbols).await {
+                return self.lost(e).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn send<S: serde::Serialize>(
+    socket: &mut WebSocket,
+    action: &str,
+    symbols: &[S],
+) -> Result<(), YahooError> {
+    let message = serde_json::json!({ action: symbols }).to_string();
+    socket
+        .send(Message::text(message))
+        .await
+        .map_err(|e| YahooError::from_wreq_while(e, "subscribing to the streamer"))
+}
+
+/// The message of the second version of the streamer, wrapping the encoded pricing data
+#[derive(Deserialize)]
+struct Envelope {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+/// Decode a message of the streamer, returning `None` for the messages without pricing data.
+fn decode(text: &str) -> Result<Option<PricingData>, YahooError> {
+    let encoded = if text.trim_start().starts_with('{') {
+        let envelope: Envelope = serde_json::from_str(text)?;
+        if envelope.kind != "pricing" {
+            return Ok(None);
+        }
+        Cow::Owned(envelope.message)
+    } else {
+        Cow::Borrowed(text)
+    };
+
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| YahooError::InvalidMessage(e.to_string()))?;
+    let tick = PricingData::decode(data.as_slice())
+        .map_err(|e| YahooError::InvalidMessage(e.to_string()))?;
+    Ok(Some(tick))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // recorded frames of the first and the second version of the streamer
+    const AAPL_FRAME: &str = "CgRBQVBMFbjeZUMY0MHl2upkIgNVU0QqA05NUzAIOAFFPQoXP0j4mrMkZc3MrD8=";
+    const MSFT_FRAME: &str =
+        r#"{"type":"pricing","message":"CgRNU0ZUFQCA0EMYoNHl2upkIgNVU0QqA05NUzAIOAI="}"#;
+
+    #[test]
+    fn test_decode() {
+        let aapl = decode(AAPL_FRAME).unwrap().unwrap();
+        assert_eq!(aapl.id, "AAPL");
+        assert_eq!(aapl.price, 229.87);
+        assert_eq!(aapl.time, 1732309201000);
+        assert_eq!(aapl.day_volume, 38168252);
+        assert_eq!(aapl.quote_type(), QuoteType::Equity);
+        assert_eq!(aapl.market_hours(), MarketHoursType::RegularMarket);
+
+        let msft = decode(MSFT_FRAME).unwrap().unwrap();
+        assert_eq!(msft.id, "MSFT");
+        assert_eq!(msft.market_hours(), MarketHoursType::PostMarket);
+
+        assert!(decode(r#"{"type":"heartbeat","message":""}"#)
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            decode("not base64!"),
+            Err(YahooError::InvalidMessage(_))
+        ));
+    }
+
+    // the blocking connector must not be dropped within a runtime
+    #[cfg(not(feature = "blocking"))]
+    mod stand_in {
+        use std::time::Duration;
+
+        use futures::{SinkExt, StreamExt};
+        use tokio::net::TcpListener;
+        use tokio_tungstenite::tungstenite::Message as Frame;
+
+        use super::*;
+        use crate::YahooUrls;
+
+        async fn next_text<S>(socket: &mut S) -> String
+        where
+            S: Stream<Item = Result<Frame, tokio_tungstenite::tungstenite::Error>> + Unpin,
+        {
+            loop {
+                if let Frame::Text(text) = socket.next().await.unwrap().unwrap() {
+                    return text.to_string();
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn test_stream_quotes() {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let host = format!("http://{}", listener.local_addr().unwrap());
+
+            // stand-in for the streamer replaying the recorded frames
+            let streamer = tokio::spawn(async move {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                assert_eq!(next_text(&mut socket).await, r#"{"subscribe":["AAPL"]}"#);
+                socket.send(Frame::text(AAPL_FRAME)).await.unwrap();
+                assert_eq!(next_text(&mut socket).await, r#"{"subscribe":["MSFT"]}"#);
+                socket.send(Frame::text(MSFT_FRAME)).await.unwrap();
+                // lose the connection
+                drop(socket);
+
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                assert_eq!(
+                    next_text(&mut socket).await,
+                    r#"{"subscribe":["AAPL","MSFT"]}"#
+                );
+                socket.send(Frame::text(AAPL_FRAME)).await.unwrap();
+                assert_eq!(next_text(&mut socket).await, r#"{"unsubscribe":["AAPL"]}"#);
+                socket.send(Frame::text(MSFT_FRAME)).await.unwrap();
+            });
+
+            let provider = YahooConnector::builder()
+                .urls(YahooUrls::with_host(&host))
+                .retry_policy(
+                    RetryPolicy::new(3).backoff(Duration::from_millis(1), Duration::from_millis(1)),
+                )
+                .build()
+                .unwrap();
+            let mut quotes = provider.stream_quotes(&["AAPL"]);
+
+            assert_eq!(quotes.next().await.unwrap().unwrap().id, "AAPL");
+            quotes.subscribe(&["MSFT"]);
+            assert_eq!(quotes.next().await.unwrap().unwrap().id, "MSFT");
+            // reconnected and subscribed to both symbols again
+            assert_eq!(quotes.next().await.unwrap().unwrap().id, "AAPL");
+            quotes.unsubscribe(&["AAPL"]);
+            assert_eq!(quotes.next().await.unwrap().unwrap().id, "MSFT");
+
+            streamer.await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_reconnect_backoff() {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let host = format!("http://{}", listener.local_addr().unwrap());
+
+            // stand-in for a streamer accepting the connections and closing them right away,
+            // sending a frame before closing the third one only
+            let streamer = tokio::spawn(async move {
+                for connection in 1..=5 {
+                    let (tcp, _) = listener.accept().await.unwrap();
+                    let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                    if connection == 3 {
+                        next_text(&mut socket).await;
+                        socket.send(Frame::text(AAPL_FRAME)).await.unwrap();
+                    }
+                    socket.close(None).await.unwrap();
+                }
+                listener
+            });
+
+            let provider = YahooConnector::builder()
+                .urls(YahooUrls::with_host(&host))
+                .retry_policy(
+                    RetryPolicy::new(3)
+                        .backoff(Duration::from_millis(20), Duration::from_millis(20))
+                        .jitter(false),
+                )
+                .build()
+                .unwrap();
+            let mut quotes = provider.stream_quotes(&["AAPL"]);
+            let start = tokio::time::Instant::now();
+
+            assert_eq!(quotes.next().await.unwrap().unwrap().id, "AAPL");
+            // the frame resets the count of the lost connections
+            assert!(matches!(
+                quotes.next().await,
+                Some(Err(YahooError::MaxRetriesReached(3, _)))
+            ));
+            assert!(quotes.next().await.is_none());
+            // waited before each of the four reconnects
+            assert!(start.elapsed() >= Duration::from_millis(80));
+
+            let listener = tokio::time::timeout(Duration::from_secs(5), streamer)
+                .await
+                .unwrap()
+                .unwrap();
+            let reconnect = tokio::time::timeout(Duration::from_millis(50), listener.accept());
+            assert!(reconnect.await.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_reconnect_exhausted() {
+            // nothing is listening on the port once the listener is dropped
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let host = format!("http://{}", listener.local_addr().unwrap());
+            drop(listener);
+
+            let provider = YahooConnector::builder()
+                .urls(YahooUrls::with_host(&host))
+                .retry_policy(
+                    RetryPolicy::new(2).backoff(Duration::from_millis(1), Duration::from_millis(1)),
+                )
+                .build()
+                .unwrap();
+            let mut quotes = provider.stream_quotes(&["AAPL"]);
+
+            assert!(matches!(
+                quotes.next().await,
+                Some(Err(YahooError::MaxRetriesReached(2, _)))
+            ));
+            assert!(quotes.next().await.is_none());
+        }
+    }
+}
@@ -17,12 +17,20 @@ pub enum YahooError {
     DataInconsistency,
     #[error("construcing yahoo! finance client failed")]
     BuilderFailed,
+    #[error("creating the runtime of the blocking client failed: {0}")]
+    RuntimeFailed(#[source] std::io::Error),
     #[error("server reports too many requests while {0}: {1}")]
     TooManyRequests(String, #[source] wreq::Error),
     #[error("unexpected response while {0}: {1}")]
     UnexpectedResponse(String, #[source] wreq::Error),
-    #[error("request didn't succeed in {0} retries")]
+    #[error("request didn't succeed in {0} attempts")]
     MaxRetriesReached(u8, #[source] Box<YahooError>),
+    #[error("invalid request parameter: {0}")]
+    InvalidParameter(String),
+    #[error("using the cassette failed: {0}")]
+    CassetteFailed(String),
+    #[error("yahoo! finance streamed an invalid message: {0}")]
+    InvalidMessage(String),
 }
 
 impl YahooError {